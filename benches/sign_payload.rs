@@ -0,0 +1,63 @@
+//! CPU cost of signed and unsigned payloads, `cargo bench --bench sign_payload`.
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use http_body_util::BodyExt;
+use hyper::{HeaderMap, Method, Uri};
+use minio_rsc::error::Error;
+use minio_rsc::{sign_request_v4, Data};
+
+const ACCESS_KEY: &str = "minio-access-key-test";
+const SECRET_KEY: &str = "minio-secret-key-test";
+const SIZE: usize = 16 * 1024 * 1024;
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn data(stream: bool) -> Data<Error> {
+    let bytes = Bytes::from(vec![b'a'; SIZE]);
+    if stream {
+        let chunks: Vec<Result<Bytes, Error>> = (0..SIZE)
+            .step_by(CHUNK_SIZE)
+            .map(|i| Ok(bytes.slice(i..i + CHUNK_SIZE)))
+            .collect();
+        Data::Stream(Box::pin(futures::stream::iter(chunks)), SIZE)
+    } else {
+        Data::Bytes(bytes)
+    }
+}
+
+/// Sign the request and drain the body, so the cost of chunk signing is counted.
+async fn sign(stream: bool, unsigned: bool) {
+    let uri: Uri = "https://localhost:9000/bucket/key".parse().unwrap();
+    let mut headers = HeaderMap::new();
+    if unsigned {
+        headers.insert("x-amz-content-sha256", "UNSIGNED-PAYLOAD".parse().unwrap());
+    }
+    let (_, body) = sign_request_v4(
+        &Method::PUT,
+        &uri,
+        &mut headers,
+        "us-east-1",
+        data(stream),
+        ACCESS_KEY,
+        SECRET_KEY,
+    )
+    .unwrap();
+    body.collect().await.unwrap();
+}
+
+fn bench_sign_payload(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("sign_payload");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    for (name, stream) in [("bytes", false), ("stream", true)] {
+        group.bench_with_input(BenchmarkId::new("signed", name), &stream, |b, &stream| {
+            b.to_async(&runtime).iter(|| sign(stream, false))
+        });
+        group.bench_with_input(BenchmarkId::new("unsigned", name), &stream, |b, &stream| {
+            b.to_async(&runtime).iter(|| sign(stream, true))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sign_payload);
+criterion_main!(benches);
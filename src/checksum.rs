@@ -0,0 +1,109 @@
+//! Checksums of the `x-amz-checksum-*` headers.
+use base64::Engine;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::datatype::ChecksumAlgorithm;
+use crate::error::ValueError;
+use crate::utils::base64_encode;
+
+/// Incremental checksum of a payload.
+pub(crate) enum Checksum {
+    Crc32(crc32fast::Hasher),
+    Crc32c(u32),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl Checksum {
+    pub fn new(algorithm: &ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::CRC32 => Self::Crc32(crc32fast::Hasher::new()),
+            ChecksumAlgorithm::CRC32C => Self::Crc32c(0),
+            ChecksumAlgorithm::SHA1 => Self::Sha1(Sha1::new()),
+            ChecksumAlgorithm::SHA256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Crc32(hasher) => hasher.update(data),
+            Self::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, data),
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Returns the raw digest, CRCs are encoded big-endian.
+    pub fn digest(self) -> Vec<u8> {
+        match self {
+            Self::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            Self::Crc32c(crc) => crc.to_be_bytes().to_vec(),
+            Self::Sha1(hasher) => hasher.finalize().to_vec(),
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+        }
+    }
+
+    /// Returns the base64 encoded digest, the value of the `x-amz-checksum-*` header.
+    pub fn finalize(self) -> String {
+        base64_encode(self.digest())
+    }
+}
+
+/// Returns the base64 encoded checksum of data.
+pub(crate) fn checksum(algorithm: &ChecksumAlgorithm, data: &[u8]) -> String {
+    let mut checksum = Checksum::new(algorithm);
+    checksum.update(data);
+    checksum.finalize()
+}
+
+/// Returns the checksum of a multipart object, computed from the base64 encoded checksums of its parts.
+///
+/// It is the checksum of the concatenated part digests, followed by `-` and the number of parts.
+pub(crate) fn composite_checksum<S: AsRef<str>>(
+    algorithm: &ChecksumAlgorithm,
+    part_checksums: &[S],
+) -> Result<String, ValueError> {
+    let mut checksum = Checksum::new(algorithm);
+    for part in part_checksums {
+        let digest = base64::engine::general_purpose::STANDARD
+            .decode(part.as_ref())
+            .map_err(|_| ValueError::from("invalid base64 encoded part checksum"))?;
+        checksum.update(&digest);
+    }
+    Ok(format!("{}-{}", checksum.finalize(), part_checksums.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, composite_checksum};
+    use crate::datatype::ChecksumAlgorithm;
+
+    #[test]
+    fn test_checksum() {
+        let data = b"Hello world";
+        assert_eq!(checksum(&ChecksumAlgorithm::CRC32, data), "i9aeUg==");
+        assert_eq!(checksum(&ChecksumAlgorithm::CRC32C, data), "crUfeA==");
+        assert_eq!(
+            checksum(&ChecksumAlgorithm::SHA1, data),
+            "e1AsOh9IyGCa4hLN+2Od7jlnP14="
+        );
+        assert_eq!(
+            checksum(&ChecksumAlgorithm::SHA256, data),
+            "ZOyIygCyaOW6GjVnihtTFtIS9PNmskdyMlNKiuyjfzw="
+        );
+    }
+
+    #[test]
+    fn test_composite_checksum() {
+        let parts = [
+            checksum(&ChecksumAlgorithm::CRC32, b"Hello "),
+            checksum(&ChecksumAlgorithm::CRC32, b"world"),
+        ];
+        assert_eq!(
+            composite_checksum(&ChecksumAlgorithm::CRC32, &parts).unwrap(),
+            "IDnaDw==-2"
+        );
+        assert!(composite_checksum(&ChecksumAlgorithm::CRC32, &["%%"]).is_err());
+    }
+}
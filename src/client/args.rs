@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 
 use hyper::{
-    header::{HeaderName, IntoHeaderName},
+    header::{HeaderName, IntoHeaderName, InvalidHeaderValue},
     HeaderMap,
 };
 
 use crate::{
     datatype::{
-        FromXml, InitiateMultipartUploadResult, ObjectLockConfiguration, RetentionMode, Tagging,
-        ToXml,
+        ChecksumAlgorithm, FromXml, InitiateMultipartUploadResult, ObjectLockConfiguration,
+        RetentionMode, Tagging, ToXml,
     },
     error::Result,
     sse::{Sse, SseCustomerKey},
@@ -220,6 +220,8 @@ impl CopySource {
 /// - `offset`: *Optional*, Start byte position of object data.
 /// - `length`: *Optional*, Number of bytes of object data from offset.
 /// - `metadata`: *Optional*, user-defined metadata.
+/// - `checksum_algorithm`: *Optional*, Algorithm of the checksum sent with the object data.
+/// - `checksum_mode`: *Optional*, Retrieve the checksum of the object.
 /// - `extra_headers`: *Optional*, Extra headers for advanced usage.
 ///
 /// **Note**: Some parameters are only valid in specific methods
@@ -233,6 +235,8 @@ pub struct KeyArgs {
     pub(crate) length: usize,
     pub(crate) extra_headers: Option<HeaderMap>,
     pub(crate) metadata: HashMap<String, String>,
+    pub(crate) checksum_algorithm: Option<ChecksumAlgorithm>,
+    pub(crate) checksum_mode: bool,
 }
 
 impl KeyArgs {
@@ -246,6 +250,8 @@ impl KeyArgs {
             offset: 0,
             length: 0,
             metadata: Default::default(),
+            checksum_algorithm: None,
+            checksum_mode: false,
         }
     }
 
@@ -318,6 +324,22 @@ impl KeyArgs {
         self
     }
 
+    /// Set the algorithm of the checksum sent with the object data when `uploading` an object.
+    ///
+    /// The server rejects the upload if the data doesn't match the checksum.
+    pub fn checksum_algorithm(mut self, checksum_algorithm: Option<ChecksumAlgorithm>) -> Self {
+        self.checksum_algorithm = checksum_algorithm;
+        self
+    }
+
+    /// Set whether to retrieve the checksum of the object when `get_object` or `stat_object`.
+    ///
+    /// Default: false
+    pub fn checksum_mode(mut self, checksum_mode: bool) -> Self {
+        self.checksum_mode = checksum_mode;
+        self
+    }
+
     /// Returns the metadata header of this [`ObjectArgs`].
     pub(crate) fn get_metadata_header(&self) -> Result<HeaderMap> {
         let mut meta_header: HeaderMap = HeaderMap::new();
@@ -619,6 +641,7 @@ pub struct MultipartUploadTask {
     bucket_owner: Option<String>,
     content_type: Option<String>,
     ssec_header: Option<HeaderMap>,
+    checksum_algorithm: Option<ChecksumAlgorithm>,
 }
 
 impl From<InitiateMultipartUploadResult> for MultipartUploadTask {
@@ -643,6 +666,7 @@ impl MultipartUploadTask {
             bucket_owner,
             content_type,
             ssec_header,
+            checksum_algorithm: None,
         }
     }
 
@@ -670,6 +694,17 @@ impl MultipartUploadTask {
         self.ssec_header.as_ref()
     }
 
+    /// The algorithm of the part checksums, `upload_part` sends them and
+    /// `complete_multipart_upload` verifies the checksum of the object.
+    pub fn checksum_algorithm(&self) -> Option<&ChecksumAlgorithm> {
+        self.checksum_algorithm.as_ref()
+    }
+
+    /// Set the algorithm of the part checksums, it must match the algorithm the upload was created with.
+    pub fn set_checksum_algorithm(&mut self, checksum_algorithm: Option<ChecksumAlgorithm>) {
+        self.checksum_algorithm = checksum_algorithm;
+    }
+
     pub(crate) fn set_ssec(&mut self, ssec: SseCustomerKey) {
         self.ssec_header = Some(ssec.headers());
     }
@@ -781,12 +816,89 @@ impl FromXml for ObjectLockConfig {
     }
 }
 
+/// Post policy of browser form uploads, see `presigned_post_policy`.
+/// ## param
+/// - bucket_name: Name of the bucket.
+/// - expiration: Expiration time of the policy.
+/// - conditions: Conditions the uploaded form must satisfy, a key condition is required.
+/// - content_length_range: *Optional*, Minimum and maximum size of the uploaded object.
+#[derive(Debug, Clone)]
+pub struct PostPolicy {
+    pub(crate) bucket_name: String,
+    pub(crate) expiration: UtcTime,
+    pub(crate) conditions: Vec<(&'static str, String, String)>,
+    pub(crate) content_length_range: Option<(usize, usize)>,
+}
+
+impl PostPolicy {
+    pub fn new<T: Into<String>>(bucket_name: T, expiration: UtcTime) -> Self {
+        Self {
+            bucket_name: bucket_name.into(),
+            expiration,
+            conditions: vec![],
+            content_length_range: None,
+        }
+    }
+
+    /// Add a condition, replacing the previous condition on the same element.
+    fn condition(mut self, op: &'static str, element: String, value: String) -> Self {
+        self.conditions.retain(|(_, e, _)| e != &element);
+        self.conditions.push((op, element, value));
+        self
+    }
+
+    /// The object name must equal `key`.
+    pub fn key<T: Into<String>>(self, key: T) -> Self {
+        self.condition("eq", "key".to_string(), key.into())
+    }
+
+    /// The object name must begin with `prefix`.
+    pub fn key_starts_with<T: Into<String>>(self, prefix: T) -> Self {
+        self.condition("starts-with", "key".to_string(), prefix.into())
+    }
+
+    pub fn content_type<T: Into<String>>(self, content_type: T) -> Self {
+        self.condition("eq", "Content-Type".to_string(), content_type.into())
+    }
+
+    pub fn content_type_starts_with<T: Into<String>>(self, prefix: T) -> Self {
+        self.condition("starts-with", "Content-Type".to_string(), prefix.into())
+    }
+
+    /// Limit the size of the uploaded object, both bounds are inclusive.
+    pub fn content_length_range(mut self, min: usize, max: usize) -> Self {
+        self.content_length_range = Some((min, max));
+        self
+    }
+
+    /// The status code returned to the client on success, one of `200`, `201` or `204`.
+    pub fn success_action_status(self, status: u16) -> Self {
+        self.condition(
+            "eq",
+            "success_action_status".to_string(),
+            status.to_string(),
+        )
+    }
+
+    /// Add user-defined metadata, sent as `x-amz-meta-<key>`.
+    pub fn metadata<K: Into<String>, V: Into<String>>(self, key: K, value: V) -> Self {
+        let key = key.into();
+        let element = if key.to_lowercase().starts_with("x-amz-meta-") {
+            key
+        } else {
+            format!("x-amz-meta-{key}")
+        };
+        self.condition("eq", element, value.into())
+    }
+}
+
 /// Custom request parameters for presigned URL
 /// ## param
 /// - bucket_name: Name of the bucket.
-/// - object_name: Object name in the bucket.
+/// - object_name: *Optional*, Object name in the bucket, absent for bucket-level URLs.
 /// - expires: Expiry in seconds; defaults to 7 days.
 /// - headers: Optional response_headers argument to specify response fields like date, size, type of file, data about server, etc.
+/// - signed_headers: Extra headers to sign, the client must send them with the same values.
 /// - request_date: Optional request_date argument to specify a different request date. Default is current date.
 /// - version_id: Version ID of the object.
 /// - querys: Extra query parameters for advanced usage.
@@ -794,30 +906,45 @@ impl FromXml for ObjectLockConfig {
 pub struct PresignedArgs {
     pub(crate) region: Option<String>,
     pub(crate) bucket_name: String,
-    pub(crate) object_name: String,
+    pub(crate) object_name: Option<String>,
     pub(crate) version_id: Option<String>,
     pub(crate) expires: usize,
     pub(crate) request_date: Option<UtcTime>,
     pub(crate) headers: Option<HeaderMap>,
+    pub(crate) signed_headers: HeaderMap,
     pub(crate) querys: QueryMap,
     pub(crate) presigned_endpoint: Option<url::Url>,
+    /// The first invalid header value, returned by [Minio::presign](crate::Minio::presign).
+    pub(crate) build_err: Option<String>,
 }
 
 impl PresignedArgs {
     pub fn new<T1: Into<String>, T2: Into<String>>(bucket_name: T1, object_name: T2) -> Self {
+        Self::for_bucket(bucket_name).object_name(object_name)
+    }
+
+    /// Presigned URL of the bucket itself, like `ListObjects`.
+    pub fn for_bucket<T: Into<String>>(bucket_name: T) -> Self {
         Self {
             region: None,
             bucket_name: bucket_name.into(),
-            object_name: object_name.into(),
+            object_name: None,
             version_id: None,
             expires: 604800,
             request_date: None,
             headers: None,
+            signed_headers: HeaderMap::new(),
             querys: QueryMap::new(),
             presigned_endpoint: None,
+            build_err: None,
         }
     }
 
+    pub fn object_name<T: Into<String>>(mut self, object_name: T) -> Self {
+        self.object_name = Some(object_name.into());
+        self
+    }
+
     pub fn region<T: Into<String>>(mut self, region: T) -> Self {
         self.region = Some(region.into());
         self
@@ -843,18 +970,48 @@ impl PresignedArgs {
         self
     }
 
+    /// Add a response header override, an invalid value fails [Minio::presign](crate::Minio::presign).
     pub fn header<K>(mut self, key: K, value: &str) -> Self
     where
         K: IntoHeaderName,
     {
-        let mut headers = self.headers.unwrap_or(HeaderMap::new());
-        if let Ok(value) = value.parse() {
-            headers.insert(key, value);
+        let mut headers = self.headers.take().unwrap_or(HeaderMap::new());
+        match value.parse() {
+            Ok(value) => {
+                headers.insert(key, value);
+            }
+            Err(e) => self.set_build_err(e),
         }
         self.headers = Some(headers);
         self
     }
 
+    /// Set extra headers to sign, like `Content-Type` or SSE-C headers.
+    pub fn signed_headers(mut self, headers: HeaderMap) -> Self {
+        self.signed_headers = headers;
+        self
+    }
+
+    /// Add an extra header to sign, the client must send it with the same value.
+    ///
+    /// An invalid value fails [Minio::presign](crate::Minio::presign).
+    pub fn signed_header<K>(mut self, key: K, value: &str) -> Self
+    where
+        K: IntoHeaderName,
+    {
+        match value.parse() {
+            Ok(value) => {
+                self.signed_headers.insert(key, value);
+            }
+            Err(e) => self.set_build_err(e),
+        }
+        self
+    }
+
+    fn set_build_err(&mut self, err: InvalidHeaderValue) {
+        self.build_err.get_or_insert_with(|| err.to_string());
+    }
+
     pub fn querys(mut self, querys: QueryMap) -> Self {
         self.querys = querys;
         self
@@ -81,6 +81,7 @@ impl Bucket {
         let bucket: BucketArgs = self.bucket.clone();
         self.client
             ._bucket_executor(bucket, Method::HEAD)
+            .operation("bucket_exists")
             .send()
             .await
             .map(|res| res.status().is_success())
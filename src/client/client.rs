@@ -1,17 +1,30 @@
 use std::str::FromStr;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
 use crate::data::Data;
 use crate::error::{Error, Result, ValueError};
-use crate::provider::Provider;
-use crate::signer::sign_request_v4;
+use crate::provider::{AwsFileProvider, McConfigProvider, Provider, StaticProvider};
+use crate::signer::{
+    sign_request_v2, sign_request_v4, sign_request_v4a, SignatureVersion, MAX_CLOCK_SKEW,
+};
+use crate::time::UtcTime;
 use crate::utils::{check_bucket_name, urlencode, _VALID_ENDPOINT};
 use crate::Credentials;
 use hyper::{header, header::HeaderValue, HeaderMap};
-use hyper::{Method, Uri};
+use hyper::{Method, StatusCode, Uri};
 use reqwest::{Body, Response};
 
-use super::{Bucket, BucketArgs};
+use super::retry::peek_error_body;
+use super::{Bucket, BucketArgs, HttpTransport, Interceptor, MetricsRecorder, RetryPolicy};
+
+/// The fields of an S3 error response telling the request time is too skewed.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SkewedError {
+    code: String,
+    server_time: Option<String>,
+}
 
 /// A `MinioBuilder` can be used to create a [`Minio`] with custom configuration.
 pub struct MinioBuilder {
@@ -24,8 +37,15 @@ pub struct MinioBuilder {
     secure: bool,
     virtual_hosted: bool,
     multi_chunked_encoding: bool,
+    unsigned_payload: bool,
+    signature_version: SignatureVersion,
+    region_set: String,
+    retry_policy: RetryPolicy,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    metrics_recorder: Option<Box<dyn MetricsRecorder>>,
     provider: Option<Box<dyn Provider>>,
-    client: Option<reqwest::Client>,
+    anonymous: bool,
+    transport: Option<Box<dyn HttpTransport>>,
 }
 
 impl MinioBuilder {
@@ -35,10 +55,17 @@ impl MinioBuilder {
             secure: true,
             virtual_hosted: false,
             multi_chunked_encoding: true,
+            unsigned_payload: false,
+            signature_version: SignatureVersion::V4,
+            region_set: "*".to_string(),
+            retry_policy: RetryPolicy::default(),
+            interceptors: Vec::new(),
+            metrics_recorder: None,
             region: "us-east-1".to_string(),
             agent: "MinIO (Linux; x86_64) minio-rs".to_string(),
             provider: None,
-            client: None,
+            anonymous: false,
+            transport: None,
         }
     }
 
@@ -90,7 +117,18 @@ impl MinioBuilder {
 
     /// Set custom http [reqwest::Client].
     pub fn client(mut self, client: reqwest::Client) -> Self {
-        self.client = Some(client);
+        self.transport = Some(Box::new(client));
+        self
+    }
+
+    /// Set the [HttpTransport] sending requests, like a [MockTransport](super::MockTransport) in tests.
+    ///
+    /// Default: a [reqwest::Client] with the `agent` header.
+    pub fn transport<T>(mut self, transport: T) -> Self
+    where
+        T: HttpTransport + 'static,
+    {
+        self.transport = Some(Box::new(transport));
         self
     }
 
@@ -115,49 +153,257 @@ impl MinioBuilder {
         self
     }
 
+    /// Set flag to skip hashing the payload, saving the CPU cost of SHA-256 on uploads.
+    ///
+    /// Single chunk bodies are sent as `UNSIGNED-PAYLOAD`, streams as
+    /// `STREAMING-UNSIGNED-PAYLOAD-TRAILER` with a CRC32 checksum trailer.
+    /// Only takes effect when `secure` is true, TLS protects the payload instead of the signature.
+    ///
+    /// Default: `false`.
+    pub fn unsigned_payload(mut self, unsigned_payload: bool) -> Self {
+        self.unsigned_payload = unsigned_payload;
+        self
+    }
+
+    /// Set the algorithm used to sign requests and presigned urls.
+    ///
+    /// Default: [SignatureVersion::V4].
+    /// ## Example
+    /// ```rust
+    /// use minio_rsc::provider::StaticProvider;
+    /// use minio_rsc::{Minio, SignatureVersion};
+    ///
+    /// let minio = Minio::builder()
+    ///     .endpoint("mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com")
+    ///     .provider(StaticProvider::new("access-key", "secret-key", None))
+    ///     .signature_version(SignatureVersion::V4A)
+    ///     .region_set("us-east-1,eu-west-1")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(minio.signature_version(), SignatureVersion::V4A);
+    /// ```
+    pub fn signature_version(mut self, signature_version: SignatureVersion) -> Self {
+        self.signature_version = signature_version;
+        self
+    }
+
+    /// Set the comma separated regions a [SignatureVersion::V4A] signature is valid in,
+    /// like `us-east-1,us-west-2`.
+    ///
+    /// Default: `*`, all regions.
+    pub fn region_set<T: Into<String>>(mut self, region_set: T) -> Self {
+        self.region_set = region_set.into();
+        self
+    }
+
+    /// Set how failed requests are retried, see [RetryPolicy].
+    ///
+    /// Overridden per request by [BaseExecutor::retry_policy](super::BaseExecutor::retry_policy).
+    ///
+    /// Default: [RetryPolicy::default], 3 attempts.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Add an [Interceptor] called on every request, after the ones added before.
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor + 'static,
+    {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Set the [MetricsRecorder] called with the metrics of every S3 operation.
+    ///
+    /// With the `metrics` feature, use [MetricsCrateRecorder](super::MetricsCrateRecorder)
+    /// to export them through the `metrics` crate.
+    pub fn metrics_recorder<M>(mut self, recorder: M) -> Self
+    where
+        M: MetricsRecorder + 'static,
+    {
+        self.metrics_recorder = Some(Box::new(recorder));
+        self
+    }
+
     /// Set credentials provider of your account in S3 service.
     ///
+    /// The provider is called on every request,
+    /// wrap it in a [CachingProvider](crate::provider::CachingProvider) to reuse credentials.
+    ///
     /// **Required**.
     pub fn provider<P>(mut self, provider: P) -> Self
     where
         P: Provider + 'static,
     {
         self.provider = Some(Box::new(provider));
+        self.anonymous = false;
         self
     }
 
+    /// Build an anonymous client, requests are sent unsigned without credentials.
+    ///
+    /// Useful to read public buckets, or to hit health endpoints.
+    /// Presigning is not available on an anonymous client.
+    /// ## Example
+    /// ```rust
+    /// use minio_rsc::Minio;
+    ///
+    /// let minio = Minio::builder()
+    ///     .endpoint("localhost:9022")
+    ///     .anonymous()
+    ///     .secure(false)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(minio.is_anonymous());
+    /// ```
+    pub fn anonymous(mut self) -> Self {
+        self.provider = None;
+        self.anonymous = true;
+        self
+    }
+
+    /// Configure from a profile of the AWS shared credentials and config file.
+    ///
+    /// Uses the profile credentials, loaded once, as provider,
+    /// and picks up `region` and `endpoint_url` if present.
+    /// Without credentials in the profile, `provider` reads the files on every fetch.
+    /// ## Example
+    /// ```rust,no_run
+    /// use minio_rsc::provider::AwsFileProvider;
+    /// use minio_rsc::Minio;
+    ///
+    /// let minio = Minio::builder()
+    ///     .aws_profile(AwsFileProvider::new().profile("staging"))
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn aws_profile(
+        mut self,
+        provider: AwsFileProvider,
+    ) -> std::result::Result<Self, ValueError> {
+        let profile = provider.load()?;
+        if let Some(region) = profile.region {
+            self.region = region;
+        }
+        if let Some(endpoint_url) = profile.endpoint_url {
+            self = self.endpoint_url(&endpoint_url)?;
+        }
+        Ok(match (profile.access_key, profile.secret_key) {
+            (Some(ak), Some(sk)) => {
+                self.provider(StaticProvider::new(ak, sk, profile.session_token))
+            }
+            _ => self.provider(provider),
+        })
+    }
+
+    /// Create a `MinioBuilder` from an alias of the MinIO client (mc) `~/.mc/config.json`.
+    ///
+    /// Sets endpoint, secure flag, bucket lookup style, signature version and credentials provider.
+    ///
+    /// The alias `api` selects the signature version, `S3v2` for [SignatureVersion::V2],
+    /// `S3v4` for [SignatureVersion::V4].
+    /// ## Example
+    /// ```rust,no_run
+    /// use minio_rsc::client::MinioBuilder;
+    ///
+    /// let minio = MinioBuilder::from_mc_alias("myminio")
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn from_mc_alias<T: Into<String>>(alias: T) -> std::result::Result<Self, ValueError> {
+        Self::new().mc_alias(McConfigProvider::new(alias))
+    }
+
+    /// Configure from an alias of the MinIO client (mc) config file.
+    ///
+    /// The alias credentials are loaded once, without them `provider` reads the file on every fetch.
+    /// See [MinioBuilder::from_mc_alias].
+    pub fn mc_alias(mut self, provider: McConfigProvider) -> std::result::Result<Self, ValueError> {
+        let alias = provider.load()?;
+        self = self.endpoint_url(&alias.url)?;
+        match alias.path.as_str() {
+            "on" => self.virtual_hosted = false,
+            "off" => self.virtual_hosted = true,
+            _ => {}
+        }
+        match alias.api.to_ascii_lowercase().as_str() {
+            "s3v2" => self.signature_version = SignatureVersion::V2,
+            "s3v4" => self.signature_version = SignatureVersion::V4,
+            _ => {}
+        }
+        Ok(if alias.credentials().is_some() {
+            self.provider(StaticProvider::new(
+                alias.access_key,
+                alias.secret_key,
+                None,
+            ))
+        } else {
+            self.provider(provider)
+        })
+    }
+
+    /// Set endpoint and secure flag from a url like `http://localhost:9000`.
+    fn endpoint_url(mut self, endpoint_url: &str) -> std::result::Result<Self, ValueError> {
+        let url =
+            url::Url::parse(endpoint_url).map_err(|_| ValueError::from("Invalid endpoint url"))?;
+        let host = url.host_str().ok_or("Invalid endpoint url")?;
+        self.secure = url.scheme() == "https";
+        self.endpoint = Some(match url.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        });
+        Ok(self)
+    }
+
     pub fn build(self) -> std::result::Result<Minio, ValueError> {
         let endpoint = self.endpoint.ok_or("Miss endpoint")?;
         if !_VALID_ENDPOINT.is_match(&endpoint) {
             return Err("Invalid endpoint".into());
         }
-        let provider = self.provider.ok_or("Miss provide")?;
+        let provider = if self.anonymous {
+            None
+        } else {
+            Some(self.provider.ok_or("Miss provide")?)
+        };
 
         let agent: HeaderValue = self
             .agent
             .parse()
             .map_err(|_| ValueError::from("Invalid agent"))?;
 
-        let client2 = self.client.unwrap_or_else(|| {
+        let transport = self.transport.unwrap_or_else(|| {
             let mut headers = header::HeaderMap::new();
             headers.insert(header::USER_AGENT, agent.clone());
-            reqwest::Client::builder()
-                .default_headers(headers)
-                .https_only(self.secure)
-                .max_tls_version(reqwest::tls::Version::TLS_1_2)
-                .build()
-                .unwrap()
+            Box::new(
+                reqwest::Client::builder()
+                    .default_headers(headers)
+                    .https_only(self.secure)
+                    .max_tls_version(reqwest::tls::Version::TLS_1_2)
+                    .build()
+                    .unwrap(),
+            )
         });
         Ok(Minio {
             inner: Arc::new(MinioRef {
                 endpoint,
                 secure: self.secure,
-                client2,
+                transport,
                 virtual_hosted: self.virtual_hosted,
                 multi_chunked: self.multi_chunked_encoding,
+                unsigned_payload: self.unsigned_payload && self.secure,
+                signature_version: self.signature_version,
+                region_set: self.region_set,
+                retry_policy: self.retry_policy,
+                interceptors: self.interceptors,
+                metrics_recorder: self.metrics_recorder,
                 region: self.region,
                 agent,
                 provider,
+                clock_offset: AtomicI64::new(0),
             }),
         })
     }
@@ -189,11 +435,18 @@ struct MinioRef {
     endpoint: String,
     virtual_hosted: bool,
     multi_chunked: bool,
+    unsigned_payload: bool,
+    signature_version: SignatureVersion,
+    region_set: String,
+    retry_policy: RetryPolicy,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    metrics_recorder: Option<Box<dyn MetricsRecorder>>,
     secure: bool,
-    client2: reqwest::Client,
+    transport: Box<dyn HttpTransport>,
     region: String,
     agent: HeaderValue,
-    provider: Box<dyn Provider>,
+    provider: Option<Box<dyn Provider>>,
+    clock_offset: AtomicI64,
 }
 
 impl Minio {
@@ -215,9 +468,95 @@ impl Minio {
         self.inner.region.clone()
     }
 
+    /// The algorithm used to sign requests, see [MinioBuilder::signature_version].
+    pub fn signature_version(&self) -> SignatureVersion {
+        self.inner.signature_version
+    }
+
+    /// return whether the minio uses virtual-hosted–style.
+    pub(crate) fn virtual_hosted(&self) -> bool {
+        self.inner.virtual_hosted
+    }
+
+    /// How failed requests are retried, see [MinioBuilder::retry_policy].
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.inner.retry_policy
+    }
+
+    pub(crate) fn metrics_recorder(&self) -> Option<&dyn MetricsRecorder> {
+        self.inner.metrics_recorder.as_deref()
+    }
+
+    pub(crate) fn region_set(&self) -> &str {
+        &self.inner.region_set
+    }
+
+    /// The seconds the server clock is ahead of the local clock, negative if behind.
+    ///
+    /// Zero until the server rejects a request as `RequestTimeTooSkewed`,
+    /// then requests and presigned urls are signed at the local time plus the offset.
+    pub fn clock_offset(&self) -> i64 {
+        self.inner.clock_offset.load(Ordering::Relaxed)
+    }
+
+    /// The current time of the server, the time requests are signed at.
+    pub(crate) fn server_time(&self) -> UtcTime {
+        UtcTime::now().add_seconds(self.clock_offset())
+    }
+
+    /// Store the clock offset if the `response` rejects a request signed at `date` as too skewed.
+    ///
+    /// The server answers `403 RequestTimeTooSkewed` when the signing time is over
+    /// [MAX_CLOCK_SKEW] seconds away from its clock, the server time is taken from
+    /// the `ServerTime` of the error, else from the `Date` header.
+    /// A 403 without error code, like the answer to a `HEAD` request,
+    /// is taken as too skewed if its `Date` header is over [MAX_CLOCK_SKEW] seconds away.
+    ///
+    /// Return the response, its body is read for the error code, and whether the offset was corrected.
+    async fn correct_clock_skew(
+        &self,
+        response: Response,
+        date: &UtcTime,
+    ) -> Result<(Response, bool)> {
+        if response.status() != StatusCode::FORBIDDEN {
+            return Ok((response, false));
+        }
+        let header_time = response
+            .headers()
+            .get(header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(UtcTime::from_http_format_time);
+        let (response, body) = peek_error_body(response).await?;
+        let server_time = match crate::xml::de::from_reader::<_, SkewedError>(body.as_ref()) {
+            Ok(e) if e.code == "RequestTimeTooSkewed" => e
+                .server_time
+                .and_then(|t| t.parse::<chrono::DateTime<chrono::Utc>>().ok())
+                .map(UtcTime::new)
+                .or(header_time),
+            Ok(_) => None,
+            Err(_) => {
+                header_time.filter(|t| (t.timestamp() - date.timestamp()).abs() > MAX_CLOCK_SKEW)
+            }
+        };
+        let Some(server_time) = server_time else {
+            return Ok((response, false));
+        };
+        let offset = server_time.timestamp() - UtcTime::now().timestamp();
+        self.inner.clock_offset.store(offset, Ordering::Relaxed);
+        Ok((response, true))
+    }
+
+    /// Whether this client sends unsigned requests, see [MinioBuilder::anonymous].
+    pub fn is_anonymous(&self) -> bool {
+        self.inner.provider.is_none()
+    }
+
     #[inline]
-    pub(super) async fn fetch_credentials(&self) -> Credentials {
-        self.inner.provider.fetch().await
+    pub(super) async fn fetch_credentials(&self) -> Result<Credentials> {
+        match &self.inner.provider {
+            Some(provider) => provider.fetch().await,
+            None => Err(ValueError::from("Anonymous client has no credentials"))?,
+        }
     }
 
     /// Execute HTTP request.
@@ -228,15 +567,53 @@ impl Minio {
         headers: HeaderMap,
         body: Body,
     ) -> Result<Response> {
-        let request = self
-            .inner
-            .client2
-            .request(method, uri)
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-        Ok(request)
+        let send = self.inner.transport.send(method, uri, headers, body);
+        #[cfg(feature = "tracing")]
+        let send = tracing::Instrument::instrument(send, tracing::debug_span!("send"));
+        send.await
+    }
+
+    /// Call [Interceptor::before_sign] of the interceptors, until one answers the request.
+    #[allow(clippy::result_large_err)]
+    fn _before_sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<Response>> {
+        for interceptor in &self.inner.interceptors {
+            if let Some(response) = interceptor.before_sign(method, uri, headers)? {
+                return Ok(Some(response));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Call [Interceptor::after_sign] of the interceptors, until one answers the request.
+    #[allow(clippy::result_large_err)]
+    fn _after_sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<Response>> {
+        for interceptor in &self.inner.interceptors {
+            if let Some(response) = interceptor.after_sign(method, uri, headers)? {
+                return Ok(Some(response));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Pass the response through [Interceptor::after_response] of the interceptors.
+    #[allow(clippy::result_large_err)]
+    fn _after_response(&self, method: &Method, uri: &Uri, response: Response) -> Result<Response> {
+        self.inner
+            .interceptors
+            .iter()
+            .try_fold(response, |response, interceptor| {
+                interceptor.after_response(method, uri, response)
+            })
     }
 
     #[inline]
@@ -327,6 +704,8 @@ impl Minio {
                 Err(ValueError::from("Miss bucket name."))?
             }
         }
+        // the bucket is in the host of a virtual-hosted–style uri, signature V2 signs it apart.
+        let virtual_bucket = bucket_name.clone().filter(|_| self.inner.virtual_hosted);
         // build uri
         let uri = self._build_uri(bucket_name, object_name);
 
@@ -337,23 +716,112 @@ impl Minio {
             uri
         };
         let mut data = data.into();
-        if !self.inner.multi_chunked {
+        // signature V4A doesn't sign chunks, a stream is only sent unsigned over TLS.
+        let v4a_plain =
+            self.inner.signature_version == SignatureVersion::V4A && !uri.starts_with("https://");
+        if !self.inner.multi_chunked || v4a_plain {
             data = data.convert().await?;
         }
         let mut headers = headers.unwrap_or(HeaderMap::new());
         headers.insert(header::USER_AGENT, self.inner.agent.clone());
-        let credentials = self.fetch_credentials().await;
         let uri = Uri::from_str(&uri).map_err(|e| Error::ValueError(e.to_string()))?;
-        let (uri, body) = sign_request_v4(
-            &method,
-            &uri,
-            &mut headers,
-            region,
-            data,
-            credentials.access_key(),
-            credentials.secret_key(),
-        )?;
-        self._url_open(method, uri, headers, body).await
+        if self.is_anonymous() {
+            // the trailer needs a signed aws-chunked payload.
+            headers.remove("x-amz-trailer");
+            if let Some(response) = self._before_sign(&method, &uri, &mut headers)? {
+                return Ok(response);
+            }
+            let body = match data {
+                Data::Bytes(b) => Body::from(b),
+                Data::Stream(s, len) => {
+                    headers.insert(header::CONTENT_LENGTH, len.into());
+                    Body::wrap_stream(s)
+                }
+            };
+            if let Some(response) = self._after_sign(&method, &uri, &mut headers)? {
+                return Ok(response);
+            }
+            let response = self
+                ._url_open(method.clone(), uri.to_string(), headers, body)
+                .await?;
+            return self._after_response(&method, &uri, response);
+        }
+        let credentials = self.fetch_credentials().await?;
+        if let Some(token) = credentials.session_token() {
+            headers.insert("x-amz-security-token", token.parse()?);
+        }
+        if self.inner.unsigned_payload
+            && self.inner.signature_version != SignatureVersion::V2
+            && !headers.contains_key("x-amz-content-sha256")
+        {
+            headers.insert(
+                "x-amz-content-sha256",
+                HeaderValue::from_static("UNSIGNED-PAYLOAD"),
+            );
+        }
+        // a request with a bytes body is sent again once if the clock skew is corrected.
+        let mut replay = match &data {
+            Data::Bytes(bytes) => Some((headers.clone(), bytes.clone())),
+            Data::Stream(..) => None,
+        };
+        loop {
+            let date = self.server_time();
+            if self.inner.signature_version == SignatureVersion::V2 {
+                headers.insert(header::DATE, date.http_format_time().parse()?);
+            } else {
+                headers.insert("x-amz-date", date.aws_format_time().parse()?);
+            }
+            if let Some(response) = self._before_sign(&method, &uri, &mut headers)? {
+                return Ok(response);
+            }
+            let (url, body) = {
+                #[cfg(feature = "tracing")]
+                let _span =
+                    tracing::debug_span!("sign", version = ?self.inner.signature_version).entered();
+                match self.inner.signature_version {
+                    SignatureVersion::V4 => sign_request_v4(
+                        &method,
+                        &uri,
+                        &mut headers,
+                        region,
+                        data,
+                        credentials.access_key(),
+                        credentials.secret_key(),
+                    )?,
+                    SignatureVersion::V4A => sign_request_v4a(
+                        &method,
+                        &uri,
+                        &mut headers,
+                        &self.inner.region_set,
+                        data,
+                        credentials.access_key(),
+                        credentials.secret_key(),
+                    )?,
+                    SignatureVersion::V2 => sign_request_v2(
+                        &method,
+                        &uri,
+                        &mut headers,
+                        virtual_bucket.as_deref(),
+                        data,
+                        credentials.access_key(),
+                        credentials.secret_key(),
+                    )?,
+                }
+            };
+            if let Some(response) = self._after_sign(&method, &uri, &mut headers)? {
+                return Ok(response);
+            }
+            let response = self._url_open(method.clone(), url, headers, body).await?;
+            let (response, corrected) = self.correct_clock_skew(response, &date).await?;
+            let response = self._after_response(&method, &uri, response)?;
+            match replay.take() {
+                Some((h, bytes)) if corrected => {
+                    headers = h;
+                    data = Data::Bytes(bytes);
+                }
+                _ => return Ok(response),
+            }
+        }
     }
 
     #[inline]
@@ -1,11 +1,18 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+
 use bytes::Bytes;
-use hyper::header::{HeaderName, HeaderValue};
-use hyper::{HeaderMap, Method};
-use reqwest::Response;
+use futures::{Stream, StreamExt};
+use http_body_util::BodyDataStream;
+use hyper::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use hyper::{HeaderMap, Method, StatusCode};
+use reqwest::{Body, Response};
 
-use super::{Minio, QueryMap};
+use super::retry::{peek_error_code, response_from_parts};
+use super::{Minio, QueryMap, RequestMetrics, RetryPolicy};
 use crate::data::Data;
-use crate::datatype::{FromXml, ToXml};
+use crate::datatype::{ChecksumAlgorithm, FromXml, ToXml};
 use crate::error::{Error, Result, S3Error};
 use crate::utils::md5sum_hash;
 
@@ -40,32 +47,46 @@ use crate::utils::md5sum_hash;
 /// }
 /// ```
 pub struct BaseExecutor<'a> {
+    operation: &'static str,
     method: Method,
     region: String,
     bucket_name: Option<String>,
     object_name: Option<String>,
     body: Data<Error>,
+    rewind: Option<Box<dyn Fn() -> Data<Error> + Send + Sync + 'a>>,
     headers: HeaderMap,
     querys: QueryMap,
     client: &'a Minio,
+    retry_policy: Option<RetryPolicy>,
     build_err: Result<()>,
 }
 
 impl<'a> BaseExecutor<'a> {
     pub fn new(method: Method, client: &'a Minio) -> Self {
         return Self {
+            operation: "request",
             method,
             region: client.region().to_string(),
             bucket_name: None,
             object_name: None,
             body: Default::default(),
+            rewind: None,
             headers: HeaderMap::new(),
             client,
             querys: QueryMap::new(),
+            retry_policy: None,
             build_err: Ok(()),
         };
     }
 
+    /// Set the name of the S3 operation, like `put_object`, recorded by tracing and metrics.
+    ///
+    /// Default: `request`.
+    pub fn operation(mut self, operation: &'static str) -> Self {
+        self.operation = operation;
+        self
+    }
+
     /// Set the request method.
     pub fn method(mut self, method: Method) -> Self {
         self.method = method;
@@ -93,6 +114,49 @@ impl<'a> BaseExecutor<'a> {
     /// Set the request body.
     pub fn body<B: Into<Data<Error>>>(mut self, body: B) -> Self {
         self.body = body.into();
+        self.rewind = None;
+        self
+    }
+
+    /// Set the request body created by `body`, called again for each retry of the request.
+    ///
+    /// A [Data::Stream] body set by [BaseExecutor::body] is not retried, as it can only be read once.
+    /// ## Example
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use hyper::Method;
+    /// use minio_rsc::error::Result;
+    /// use minio_rsc::{Data, Minio};
+    ///
+    /// async fn put_object(minio: Minio, chunks: Vec<Bytes>) -> Result<()> {
+    ///     let len = chunks.iter().map(|c| c.len()).sum();
+    ///     minio
+    ///         .executor(Method::PUT)
+    ///         .bucket_name("bucket")
+    ///         .object_name("test.txt")
+    ///         .rewindable_body(move || {
+    ///             let chunks = chunks.clone().into_iter().map(Ok);
+    ///             Data::Stream(Box::pin(stream::iter(chunks)), len)
+    ///         })
+    ///         .send_ok()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn rewindable_body<F, B>(mut self, body: F) -> Self
+    where
+        F: Fn() -> B + Send + Sync + 'a,
+        B: Into<Data<Error>>,
+    {
+        self.body = body().into();
+        self.rewind = Some(Box::new(move || body().into()));
+        self
+    }
+
+    /// Set how this request is retried, overriding [Minio::retry_policy].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
         self
     }
 
@@ -113,6 +177,13 @@ impl<'a> BaseExecutor<'a> {
         self.body(body).header("Content-MD5", md5)
     }
 
+    /// Send the checksum of the body with the request, see [crate::sign_request_v4].
+    ///
+    /// A streaming body carries it in a signed trailer, so it is not buffered in memory.
+    pub fn trailing_checksum(self, algorithm: ChecksumAlgorithm) -> Self {
+        self.header("x-amz-trailer", algorithm.header_name())
+    }
+
     /// Set the new request header.
     pub fn headers(mut self, header: HeaderMap) -> Self {
         self.headers = header;
@@ -189,21 +260,141 @@ impl<'a> BaseExecutor<'a> {
 
     /// Send an HTTP request to S3 and return a Result<[Response]>.
     ///
+    /// The request is retried on transient failures, see [RetryPolicy].
+    ///
+    /// The metrics of the request are recorded once the response body is read to the end,
+    /// or dropped, see [MetricsRecorder](super::MetricsRecorder).
+    ///
     /// note: this is just a response from the s3 service, probably a wrong response.
     pub async fn send(self) -> Result<Response> {
-        self.build_err?;
+        let client = self.client;
+        let operation = self.operation;
+        let method = self.method.clone();
+        let bucket = self.bucket_name.clone();
+        let bytes_sent = self.body.len() as u64;
+        let start = Instant::now();
+        #[cfg(feature = "tracing")]
+        let (result, retries, span) = {
+            use tracing::field::Empty;
+            let span = tracing::info_span!(
+                "s3_operation",
+                operation = self.operation,
+                bucket = self.bucket_name.as_deref(),
+                key = self.object_name.as_deref(),
+                method = %self.method,
+                region = %self.region,
+                bytes_sent = bytes_sent,
+                status = Empty,
+                bytes_received = Empty,
+                request_id = Empty,
+                retries = Empty,
+                latency_ms = Empty,
+                error = Empty,
+            );
+            let (result, retries) =
+                tracing::Instrument::instrument(self._send(), span.clone()).await;
+            record_span(&span, &result, retries, start);
+            (result, retries, span)
+        };
+        #[cfg(not(feature = "tracing"))]
+        let (result, retries) = self._send().await;
+
+        let (res, metrics) = match client.metrics_recorder() {
+            Some(_) => {
+                let latency = start.elapsed();
+                let (result, error_code) = failure_code(result).await;
+                let metrics = PendingMetrics {
+                    client: client.clone(),
+                    operation,
+                    bucket,
+                    method,
+                    status: result.as_ref().ok().map(|res| res.status().as_u16()),
+                    error_code,
+                    latency,
+                    bytes_sent,
+                    retries,
+                };
+                match result {
+                    Ok(res) => (res, Some(metrics)),
+                    Err(e) => {
+                        metrics.record(0);
+                        return Err(e);
+                    }
+                }
+            }
+            None => (result?, None),
+        };
+        #[cfg(feature = "tracing")]
+        if metrics.is_some() || !span.is_disabled() {
+            return Ok(BodyMeter::wrap(res, metrics, span));
+        }
+        #[cfg(not(feature = "tracing"))]
+        if metrics.is_some() {
+            return Ok(BodyMeter::wrap(res, metrics));
+        }
+        Ok(res)
+    }
+
+    /// Send the request, retrying by the retry policy.
+    ///
+    /// Returns the last result, and the number of retries.
+    async fn _send(self) -> (Result<Response>, u32) {
+        if let Err(e) = self.build_err {
+            return (Err(e), 0);
+        }
         let query = self.querys.to_query_string();
-        self.client
-            ._execute(
-                self.method,
-                &self.region,
-                self.bucket_name,
-                self.object_name,
-                self.body,
-                Some(self.headers),
-                Some(query),
-            )
-            .await
+        let policy = self
+            .retry_policy
+            .as_ref()
+            .unwrap_or(self.client.retry_policy());
+        // a stream can only be sent once, unless it is rewindable.
+        let max_attempts = match (&self.body, &self.rewind) {
+            (Data::Stream(..), None) => 1,
+            _ if !policy.is_retryable_method(&self.method) => 1,
+            _ => policy.get_max_attempts(),
+        };
+        let mut body = Some(self.body);
+        let mut attempt = 1;
+        let result = loop {
+            let data = match body.take() {
+                Some(Data::Bytes(bytes)) => {
+                    body = Some(Data::Bytes(bytes.clone()));
+                    Data::Bytes(bytes)
+                }
+                Some(data) => data,
+                None => self.rewind.as_ref().map(|f| f()).unwrap_or_default(),
+            };
+            let result = self
+                .client
+                ._execute(
+                    self.method.clone(),
+                    &self.region,
+                    self.bucket_name.clone(),
+                    self.object_name.clone(),
+                    data,
+                    Some(self.headers.clone()),
+                    Some(query.clone()),
+                )
+                .await;
+            if attempt >= max_attempts {
+                break result;
+            }
+            match result {
+                Ok(res) if RetryPolicy::is_retryable_status(res.status()) => {}
+                // S3 answers `RequestTimeout` with 400.
+                Ok(res) if res.status() == StatusCode::BAD_REQUEST => {
+                    match peek_error_code(res).await {
+                        Ok((_, Some(code))) if RetryPolicy::is_retryable_code(&code) => {}
+                        result => break result.map(|(res, _)| res),
+                    }
+                }
+                Err(e) if RetryPolicy::is_retryable_error(&e) => {}
+                result => break result,
+            }
+            futures_timer::Delay::new(policy.delay(attempt)).await;
+            attempt += 1;
+        };
+        (result, attempt - 1)
     }
 
     /// Send an HTTP request to S3 and return a Result<[Response]>.
@@ -242,3 +433,167 @@ impl<'a> BaseExecutor<'a> {
             .map_err(Into::into)
     }
 }
+
+/// Record the outcome of the request on the `s3_operation` span.
+#[cfg(feature = "tracing")]
+fn record_span(span: &tracing::Span, result: &Result<Response>, retries: u32, start: Instant) {
+    span.record("retries", retries);
+    span.record("latency_ms", start.elapsed().as_millis() as u64);
+    match result {
+        Ok(res) => {
+            span.record("status", res.status().as_u16());
+            if let Some(id) = res
+                .headers()
+                .get("x-amz-request-id")
+                .and_then(|v| v.to_str().ok())
+            {
+                span.record("request_id", id);
+            }
+        }
+        Err(e) => {
+            span.record("error", tracing::field::display(e));
+        }
+    }
+}
+
+/// The metrics of a request, recorded once its response body is read.
+struct PendingMetrics {
+    client: Minio,
+    operation: &'static str,
+    bucket: Option<String>,
+    method: Method,
+    status: Option<u16>,
+    error_code: Option<String>,
+    latency: Duration,
+    bytes_sent: u64,
+    retries: u32,
+}
+
+impl PendingMetrics {
+    fn record(self, bytes_received: u64) {
+        if let Some(recorder) = self.client.metrics_recorder() {
+            recorder.record(&RequestMetrics {
+                operation: self.operation,
+                bucket: self.bucket.as_deref(),
+                method: &self.method,
+                status: self.status,
+                error_code: self.error_code.as_deref(),
+                latency: self.latency,
+                bytes_sent: self.bytes_sent,
+                bytes_received,
+                retries: self.retries,
+            });
+        }
+    }
+}
+
+/// The body of a response, counting the bytes as they are read.
+///
+/// At the end of the body, or when it is dropped, the count is recorded
+/// as `bytes_received` of the `s3_operation` span and of the [PendingMetrics].
+/// The body is read in the `receive` span.
+struct BodyMeter {
+    body: BodyDataStream<Body>,
+    bytes: u64,
+    done: bool,
+    metrics: Option<PendingMetrics>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    receive: tracing::Span,
+}
+
+impl BodyMeter {
+    /// Read the body of the response through a meter.
+    fn wrap(
+        res: Response,
+        metrics: Option<PendingMetrics>,
+        #[cfg(feature = "tracing")] span: tracing::Span,
+    ) -> Response {
+        let url = res.url().clone();
+        let (parts, body) = hyper::Response::<Body>::from(res).into_parts();
+        let meter = BodyMeter {
+            body: BodyDataStream::new(body),
+            bytes: 0,
+            done: false,
+            metrics,
+            #[cfg(feature = "tracing")]
+            receive: span.in_scope(|| tracing::debug_span!("receive")),
+            #[cfg(feature = "tracing")]
+            span,
+        };
+        response_from_parts(parts, Body::wrap_stream(meter), url)
+    }
+
+    fn finish(&mut self) {
+        if std::mem::replace(&mut self.done, true) {
+            return;
+        }
+        #[cfg(feature = "tracing")]
+        self.span.record("bytes_received", self.bytes);
+        if let Some(metrics) = self.metrics.take() {
+            metrics.record(self.bytes);
+        }
+    }
+}
+
+impl Stream for BodyMeter {
+    type Item = reqwest::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        #[cfg(feature = "tracing")]
+        let _receive = this.receive.clone().entered();
+        let item = ready!(this.body.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(chunk)) => this.bytes += chunk.len() as u64,
+            _ => this.finish(),
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl Drop for BodyMeter {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// The code of a failed request, see [RequestMetrics::error_code].
+///
+/// The body of an error response with an XML body is read, it is returned in a new response.
+async fn failure_code(result: Result<Response>) -> (Result<Response>, Option<String>) {
+    match result {
+        Ok(res) if res.status().is_success() => (Ok(res), None),
+        Ok(res) if !has_xml_body(&res) => {
+            let status = res.status().as_str().to_string();
+            (Ok(res), Some(status))
+        }
+        Ok(res) => {
+            let status = res.status().as_str().to_string();
+            match peek_error_code(res).await {
+                Ok((res, code)) => (Ok(res), Some(code.unwrap_or(status))),
+                Err(e) => (Err(e), Some(status)),
+            }
+        }
+        Err(e) => {
+            let code = match e.inner() {
+                Error::S3Error(e) => e.code.clone(),
+                Error::HttpError(e) if e.is_timeout() => "Timeout".to_string(),
+                Error::HttpError(_) => "HttpError".to_string(),
+                _ => "ClientError".to_string(),
+            };
+            (Err(e), Some(code))
+        }
+    }
+}
+
+/// Whether the response has a non-empty XML body, like an S3 error.
+fn has_xml_body(res: &Response) -> bool {
+    let xml = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("xml"));
+    xml && res.content_length() != Some(0)
+}
@@ -0,0 +1,82 @@
+use hyper::{HeaderMap, Method, Uri};
+use reqwest::Response;
+
+use crate::error::Result;
+
+/// A hook into every request sent by [Minio](super::Minio), registered by
+/// [MinioBuilder::interceptor](super::MinioBuilder::interceptor).
+///
+/// Interceptors are called in the order they are registered.
+/// Each callback can short-circuit the request:
+/// - return an error to fail the request without sending it, or to fail it on the response.
+/// - return `Some(response)` from [Interceptor::before_sign] or [Interceptor::after_sign]
+///   to answer the request without sending it, the later interceptors are skipped.
+///
+/// A request retried by [RetryPolicy](super::RetryPolicy) goes through the interceptors again.
+/// ## Example
+/// ```rust
+/// use hyper::{HeaderMap, Method, Uri};
+/// use minio_rsc::client::Interceptor;
+/// use minio_rsc::error::Result;
+/// use minio_rsc::provider::StaticProvider;
+/// use minio_rsc::Minio;
+/// use reqwest::Response;
+///
+/// struct AuditLog;
+///
+/// impl Interceptor for AuditLog {
+///     fn before_sign(
+///         &self,
+///         _method: &Method,
+///         _uri: &Uri,
+///         headers: &mut HeaderMap,
+///     ) -> Result<Option<Response>> {
+///         headers.insert("x-request-id", "9f2c1a".parse()?);
+///         Ok(None)
+///     }
+///
+///     fn after_response(&self, method: &Method, uri: &Uri, response: Response) -> Result<Response> {
+///         println!("{} {} {}", method, uri, response.status());
+///         Ok(response)
+///     }
+/// }
+///
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(StaticProvider::new("minio-access-key-test", "minio-secret-key-test", None))
+///     .interceptor(AuditLog)
+///     .build()
+///     .unwrap();
+/// ```
+#[allow(clippy::result_large_err)]
+pub trait Interceptor: Send + Sync {
+    /// Called before the request is signed, headers added here are signed.
+    fn before_sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<Response>> {
+        let _ = (method, uri, headers);
+        Ok(None)
+    }
+
+    /// Called after the request is signed, right before it is sent.
+    ///
+    /// Headers changed here are sent as is, changing a signed header breaks the signature.
+    fn after_sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<Response>> {
+        let _ = (method, uri, headers);
+        Ok(None)
+    }
+
+    /// Called with the response of the request, returns the response passed on.
+    fn after_response(&self, method: &Method, uri: &Uri, response: Response) -> Result<Response> {
+        let _ = (method, uri);
+        Ok(response)
+    }
+}
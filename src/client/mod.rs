@@ -3,6 +3,7 @@ mod args;
 mod bucket;
 mod client;
 mod executor;
+mod interceptor;
 mod mutilpart_upload;
 mod operate_bucket;
 #[cfg(feature = "ext")]
@@ -10,16 +11,26 @@ mod operate_ext;
 mod operate_object;
 mod presigned;
 mod querymap;
+mod recorder;
 mod response;
+mod retry;
 mod select_object_reader;
+mod transport;
 
 pub use args::{
     BucketArgs, CopySource, KeyArgs, ListMultipartUploadsArgs, ListObjectVersionsArgs,
-    ListObjectsArgs, MultipartUploadTask, ObjectLockConfig, PresignedArgs, Tags,
+    ListObjectsArgs, MultipartUploadTask, ObjectLockConfig, PostPolicy, PresignedArgs, Tags,
 };
 pub use bucket::Bucket;
 pub use client::*;
 pub use executor::BaseExecutor;
+pub use interceptor::Interceptor;
 pub use querymap::QueryMap;
-pub use response::ObjectStat;
+#[cfg(feature = "metrics")]
+pub use recorder::MetricsCrateRecorder;
+pub use recorder::{MetricsRecorder, RequestMetrics};
+pub use response::{checksum_verified_stream, ObjectStat};
+pub(crate) use retry::response_from_parts;
+pub use retry::RetryPolicy;
 pub use select_object_reader::{Message, SelectObjectReader};
+pub use transport::{HttpTransport, MockResponse, MockTransport, RecordedRequest, ResponseFuture};
@@ -3,7 +3,8 @@ use hyper::{header, HeaderMap, Method};
 
 use super::args::MultipartUploadTask;
 use super::{BucketArgs, CopySource, KeyArgs, ListMultipartUploadsArgs};
-use crate::datatype::Part;
+use crate::checksum::{checksum, composite_checksum};
+use crate::datatype::{ChecksumAlgorithm, Part};
 use crate::datatype::{
     CompleteMultipartUpload, CompleteMultipartUploadResult, CopyPartResult,
     InitiateMultipartUploadResult, ListMultipartUploadsResult, ListPartsResult,
@@ -18,6 +19,7 @@ impl Minio {
     pub async fn abort_multipart_upload(&self, task: &MultipartUploadTask) -> Result<()> {
         let res = self
             .executor(Method::DELETE)
+            .operation("abort_multipart_upload")
             .bucket_name(task.bucket())
             .object_name(task.key())
             .query("uploadId", task.upload_id())
@@ -46,8 +48,19 @@ impl Minio {
         parts: Vec<Part>,
         extra_header: Option<HeaderMap>,
     ) -> Result<CompleteMultipartUploadResult> {
+        let checksum = match task.checksum_algorithm() {
+            Some(algorithm) => parts
+                .iter()
+                .map(|p| p.checksum(algorithm))
+                .collect::<Option<Vec<_>>>()
+                .map(|c| composite_checksum(algorithm, &c).map(|c| (algorithm, c)))
+                .transpose()?,
+            None => None,
+        };
         let body = CompleteMultipartUpload { parts };
-        self.executor(Method::POST)
+        let result: CompleteMultipartUploadResult = self
+            .executor(Method::POST)
+            .operation("complete_multipart_upload")
             .bucket_name(task.bucket())
             .object_name(task.key())
             .query("uploadId", task.upload_id())
@@ -62,7 +75,19 @@ impl Minio {
             .headers_merge2(task.ssec_header().cloned())
             .xml(&body)
             .send_xml_ok()
-            .await
+            .await?;
+        if let Some((algorithm, expected)) = checksum {
+            let actual = match algorithm {
+                ChecksumAlgorithm::CRC32 => result.checksum_crc32.as_ref(),
+                ChecksumAlgorithm::CRC32C => result.checksum_crc32c.as_ref(),
+                ChecksumAlgorithm::SHA1 => result.checksum_sha1.as_ref(),
+                ChecksumAlgorithm::SHA256 => result.checksum_sha256.as_ref(),
+            };
+            if actual.is_some_and(|actual| actual != &expected) {
+                return Err(ValueError::from("checksum of the object mismatch"))?;
+            }
+        }
+        Ok(result)
     }
 
     /// This action initiates a multipart upload and returns an MultipartUploadArgs.
@@ -79,8 +104,10 @@ impl Minio {
         let key: KeyArgs = key.into();
         let metadata_header: HeaderMap = key.get_metadata_header()?;
         let expected_bucket_owner = bucket.expected_bucket_owner.clone();
+        let checksum_algorithm = key.checksum_algorithm.clone();
         let mut result: MultipartUploadTask = self
             ._bucket_executor(bucket, Method::POST)
+            .operation("create_multipart_upload")
             .object_name(key.name.as_str())
             .query_string("uploads")
             .header(
@@ -91,11 +118,16 @@ impl Minio {
             .headers_merge(metadata_header)
             .headers_merge2(key.extra_headers)
             .headers_merge2(key.ssec_headers.clone())
+            .apply(|e| match &checksum_algorithm {
+                Some(algorithm) => e.header("x-amz-checksum-algorithm", algorithm.as_str()),
+                None => e,
+            })
             .send_xml_ok::<InitiateMultipartUploadResult>()
             .await
             .map(Into::into)?;
         result.set_ssec_header(key.ssec_headers);
         result.set_bucket_owner(expected_bucket_owner);
+        result.set_checksum_algorithm(checksum_algorithm);
         Ok(result)
     }
 
@@ -105,6 +137,7 @@ impl Minio {
         args: ListMultipartUploadsArgs,
     ) -> Result<ListMultipartUploadsResult> {
         self.executor(Method::GET)
+            .operation("list_multipart_uploads")
             .bucket_name(args.bucket_name())
             .querys(args.args_query_map())
             .headers(args.args_headers())
@@ -120,6 +153,7 @@ impl Minio {
         part_number_marker: Option<usize>,
     ) -> Result<ListPartsResult> {
         self.executor(Method::GET)
+            .operation("list_parts")
             .bucket_name(task.bucket())
             .object_name(task.key())
             .query("uploadId", task.upload_id())
@@ -156,8 +190,12 @@ impl Minio {
         if body.len() > MAX_PART_SIZE {
             return Err(ValueError::from("part size must be less then 5GiB."))?;
         }
+        let checksum = task
+            .checksum_algorithm()
+            .map(|algorithm| (algorithm, checksum(algorithm, &body)));
         let res = self
             .executor(Method::PUT)
+            .operation("upload_part")
             .bucket_name(task.bucket())
             .object_name(task.key())
             .query("uploadId", task.upload_id())
@@ -170,6 +208,10 @@ impl Minio {
                 }
             })
             .headers_merge2(task.ssec_header().cloned())
+            .apply(|e| match &checksum {
+                Some((algorithm, checksum)) => e.header(algorithm.header_name(), checksum),
+                None => e,
+            })
             .body(body)
             .send()
             .await?;
@@ -179,10 +221,11 @@ impl Minio {
                 .get(header::ETAG)
                 .map(|x| x.to_str().unwrap_or(""))
             {
-                Ok(Part {
-                    e_tag: s.to_string(),
-                    part_number,
-                })
+                let mut part = Part::new(part_number, s);
+                if let Some((algorithm, checksum)) = checksum {
+                    part.set_checksum(algorithm, Some(checksum));
+                }
+                Ok(part)
             } else {
                 Err(res.into())
             }
@@ -206,6 +249,7 @@ impl Minio {
             ))?;
         }
         self.executor(Method::PUT)
+            .operation("upload_part_copy")
             .bucket_name(task.bucket())
             .object_name(task.key())
             .query("uploadId", task.upload_id())
@@ -221,6 +265,6 @@ impl Minio {
             .headers_merge(copy_source.args_headers())
             .send_xml_ok()
             .await
-            .map(|CopyPartResult { e_tag }| Part { e_tag, part_number })
+            .map(|CopyPartResult { e_tag }| Part::new(part_number, e_tag))
     }
 }
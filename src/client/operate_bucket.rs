@@ -32,6 +32,7 @@ macro_rules! get_attr {
             B: Into<BucketArgs>,
         {
             self._bucket_executor(bucket.into(), Method::GET)
+                .operation(stringify!($name))
                 .query($query, "")
                 .send_xml_ok()
                 .await
@@ -48,6 +49,7 @@ macro_rules! set_attr {
             B: Into<BucketArgs>,
         {
             self._bucket_executor(bucket.into(), Method::PUT)
+                .operation(stringify!($name))
                 .query($query, "")
                 .xml(&value)
                 .send_ok()
@@ -66,6 +68,7 @@ macro_rules! del_attr {
             B: Into<BucketArgs>,
         {
             self._bucket_executor(bucket.into(), Method::DELETE)
+                .operation(stringify!($name))
                 .query($query, "")
                 .send_ok()
                 .await
@@ -118,6 +121,7 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         self._bucket_executor(bucket, Method::HEAD)
+            .operation("bucket_exists")
             .send()
             .await
             .map(|res| res.status().is_success())
@@ -134,6 +138,7 @@ impl Minio {
     pub async fn list_buckets(&self) -> Result<(Vec<Bucket>, Owner)> {
         let res = self
             .executor(Method::GET)
+            .operation("list_buckets")
             .send_xml_ok::<ListAllMyBucketsResult>()
             .await?;
         Ok((res.buckets.bucket, res.owner))
@@ -160,6 +165,7 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         self._bucket_executor(bucket, Method::GET)
+            .operation("list_object_versions")
             .querys(args.args_query_map())
             .headers_merge2(args.extra_headers)
             .send_xml_ok()
@@ -186,6 +192,7 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         self._bucket_executor(bucket, Method::GET)
+            .operation("list_objects")
             .querys(args.args_query_map())
             .headers_merge2(args.extra_headers)
             .send_xml_ok()
@@ -201,6 +208,7 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         self._bucket_executor(bucket, Method::GET)
+            .operation("get_bucket_region")
             .query("location", "")
             .send_xml_ok::<LocationConstraint>()
             .await
@@ -229,6 +237,7 @@ impl Minio {
         let region = &bucket.region.unwrap_or(self.region().to_string());
         let body = format!("<CreateBucketConfiguration><LocationConstraint>{}</LocationConstraint></CreateBucketConfiguration>",region);
         self.executor(Method::PUT)
+            .operation("make_bucket")
             .bucket_name(bucket.name)
             .headers_merge2(bucket.extra_headers)
             .apply(|e| {
@@ -269,6 +278,7 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         self._bucket_executor(bucket, Method::DELETE)
+            .operation("remove_bucket")
             .send_ok()
             .await
             .map(|_| ())
@@ -309,6 +319,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let res = self
             ._bucket_executor(bucket, Method::GET)
+            .operation("get_bucket_tags")
             .query("tagging", "")
             .send_xml_ok::<Tags>()
             .await;
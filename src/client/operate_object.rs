@@ -8,7 +8,9 @@ use futures::{Stream, StreamExt};
 use hyper::{header, HeaderMap, Method};
 use reqwest::Response;
 
+use super::response::response_checksum;
 use super::{BucketArgs, CopySource, KeyArgs, ObjectStat, SelectObjectReader, Tags};
+use crate::checksum::checksum;
 use crate::datatype::{AccessControlPolicy, LegalHold, Retention};
 use crate::datatype::{LegalHoldStatus, SelectRequest};
 use crate::error::{Error, Result, S3Error, ValueError};
@@ -84,6 +86,7 @@ impl Minio {
         K: Into<KeyArgs>,
     {
         self._object_executor(Method::PUT, bucket.into(), key.into(), true, true)?
+            .operation("copy_object")
             .headers_merge(src.args_headers())
             .send_ok()
             .await
@@ -108,6 +111,7 @@ impl Minio {
         K: Into<KeyArgs>,
         P: AsRef<Path>,
     {
+        use super::checksum_verified_stream;
         use tokio::{fs::File, io::AsyncWriteExt};
 
         let res = self.get_object(bucket, key).await?;
@@ -116,18 +120,18 @@ impl Minio {
             let s3err: S3Error = text.as_str().try_into()?;
             Err(s3err)?
         } else {
-            let mut stream = res.bytes_stream();
+            let mut stream = checksum_verified_stream(res);
             let mut file = File::create(path).await?;
             while let Some(item) = stream.next().await {
-                if let Ok(datas) = item {
-                    file.write_all(&datas).await?;
-                }
+                file.write_all(&item?).await?;
             }
             Ok(())
         }
     }
 
     /// Get [reqwest::Response] of an object.
+    ///
+    /// If [KeyArgs::checksum_mode] is set, the body can be verified with [super::checksum_verified_stream].
     /// ## Exapmle
     /// ``` rust
     /// use reqwest::Response;
@@ -138,6 +142,15 @@ impl Minio {
     /// let response: Response = minio.get_object("bucket", "file.txt").await?;
     /// let key = KeyArgs::new("file.txt").version_id(Some("cdabf31a-9752-4265-b137-6b3961fbaf9b".to_string()));
     /// let response: Response = minio.get_object("bucket", key).await?;
+    ///
+    /// use futures::StreamExt;
+    /// use minio_rsc::client::checksum_verified_stream;
+    /// let key = KeyArgs::new("file.txt").checksum_mode(true);
+    /// let response: Response = minio.get_object("bucket", key).await?;
+    /// let mut stream = checksum_verified_stream(response);
+    /// while let Some(data) = stream.next().await {
+    ///     let data = data?;
+    /// }
     /// # Ok(())
     /// # }
     /// ```
@@ -149,8 +162,15 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         let range = key.range();
+        let checksum_mode = key.checksum_mode;
         self._object_executor(Method::GET, bucket, key, true, true)?
+            .operation("get_object")
             .apply(|e| {
+                let e = if checksum_mode {
+                    e.header("x-amz-checksum-mode", "ENABLED")
+                } else {
+                    e
+                };
                 if let Some(range) = range {
                     e.header(header::RANGE, &range)
                 } else {
@@ -170,12 +190,15 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::GET, bucket, key, true, true)?
+            .operation("get_object_torrent")
             .query("torrent", "")
             .send_ok()
             .await
     }
 
     /// Uploads data to an object in a bucket.
+    ///
+    /// If [KeyArgs::checksum_algorithm] is set, the checksum of the data is sent with it.
     /// ## Exapmle
     /// ``` rust
     /// use reqwest::Response;
@@ -203,7 +226,16 @@ impl Minio {
     {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
+        let checksum = key
+            .checksum_algorithm
+            .as_ref()
+            .map(|algorithm| (algorithm.header_name(), checksum(algorithm, &data)));
         self._object_executor(Method::PUT, bucket, key, true, true)?
+            .operation("put_object")
+            .apply(|e| match checksum {
+                Some((name, checksum)) => e.header(name, checksum),
+                None => e,
+            })
             .body(data)
             .send_ok()
             .await?;
@@ -215,6 +247,9 @@ impl Minio {
     /// - len: total byte length of stream.
     /// If set None, the data will be transmitted through `multipart_upload`.
     /// otherwise the data will be transmitted in multiple chunks through an HTTP request.
+    ///
+    /// If [KeyArgs::checksum_algorithm] is set, a single request carries the checksum of the data
+    /// in a signed trailer.
     pub async fn put_object_stream<B, K>(
         &self,
         bucket: B,
@@ -233,7 +268,13 @@ impl Minio {
                 return Err(ValueError::from("max object size is 5TiB").into());
             }
             if self.multi_chunked() || len < MIN_PART_SIZE {
+                let checksum_algorithm = key.checksum_algorithm.clone();
                 self._object_executor(Method::PUT, bucket, key, true, true)?
+                    .operation("put_object_stream")
+                    .apply(|e| match checksum_algorithm {
+                        Some(algorithm) => e.trailing_checksum(algorithm),
+                        None => e,
+                    })
                     .body((stream, len))
                     .send_ok()
                     .await?;
@@ -348,6 +389,7 @@ impl Minio {
         K: Into<KeyArgs>,
     {
         self._object_executor(Method::DELETE, bucket.into(), key.into(), true, false)?
+            .operation("remove_object")
             .send_ok()
             .await
             .map(|_| ())
@@ -374,8 +416,17 @@ impl Minio {
         let key: KeyArgs = key.into();
         let bucket_name = bucket.name.clone();
         let object_name = key.name.clone();
+        let checksum_mode = key.checksum_mode;
         let res = self
             ._object_executor(Method::HEAD, bucket, key, true, false)?
+            .operation("stat_object")
+            .apply(|e| {
+                if checksum_mode {
+                    e.header("x-amz-checksum-mode", "ENABLED")
+                } else {
+                    e
+                }
+            })
             .send()
             .await?;
         if !res.status().is_success() {
@@ -424,6 +475,7 @@ impl Minio {
             version_id,
             size,
             metadata,
+            checksum: response_checksum(res_header),
         }))
     }
 
@@ -436,6 +488,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::GET, bucket, key, false, false)?
+            .operation("get_object_acl")
             .query("acl", "")
             .send_xml_ok()
             .await
@@ -451,6 +504,7 @@ impl Minio {
         let key: KeyArgs = key.into();
         let result = self
             ._object_executor(Method::GET, bucket, key, false, false)?
+            .operation("is_object_legal_hold_enabled")
             .query("legal-hold", "")
             .send_xml_ok::<LegalHold>()
             .await;
@@ -480,6 +534,7 @@ impl Minio {
             status: LegalHoldStatus::ON,
         };
         self._object_executor(Method::PUT, bucket, key, false, false)?
+            .operation("enable_object_legal_hold_enabled")
             .query("legal-hold", "")
             .xml(&legal_hold)
             .send_ok()
@@ -499,6 +554,7 @@ impl Minio {
             status: LegalHoldStatus::OFF,
         };
         self._object_executor(Method::PUT, bucket, key, false, false)?
+            .operation("disable_object_legal_hold_enabled")
             .query("legal-hold", "")
             .xml(&legal_hold)
             .send_ok()
@@ -526,6 +582,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::GET, bucket, key, false, false)?
+            .operation("get_object_tags")
             .query("tagging", "")
             .send_xml_ok()
             .await
@@ -555,6 +612,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::PUT, bucket, key, false, false)?
+            .operation("set_object_tags")
             .query("tagging", "")
             .xml(&tags.into())
             .send_ok()
@@ -580,6 +638,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::DELETE, bucket, key, false, false)?
+            .operation("del_object_tags")
             .query("tagging", "")
             .send_ok()
             .await
@@ -595,6 +654,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::GET, bucket, key, false, false)?
+            .operation("get_object_retention")
             .query("retention", "")
             .send_xml_ok()
             .await
@@ -614,6 +674,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::PUT, bucket, key, false, false)?
+            .operation("set_object_retention")
             .query("retention", "")
             .xml(&retention)
             .send_ok()
@@ -655,6 +716,7 @@ impl Minio {
         let bucket: BucketArgs = bucket.into();
         let key: KeyArgs = key.into();
         self._object_executor(Method::POST, bucket, key, true, false)?
+            .operation("select_object_content")
             .query_string("select&select-type=2")
             .xml(&request)
             .send_ok()
@@ -1,76 +1,115 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
-use hyper::HeaderMap;
 use hyper::{Method, Uri};
+use serde_json::json;
 
-use super::{PresignedArgs, QueryMap};
+use super::{MultipartUploadTask, PostPolicy, PresignedArgs};
+use crate::datatype::{CompleteMultipartUploadResult, Part};
 use crate::error::{Result, ValueError};
-use crate::signer::presign_v4;
+use crate::signer::{
+    post_presign_v4, presign_v2, presign_v4_with_headers, presign_v4a, SignatureVersion,
+    MAX_MULTIPART_COUNT,
+};
 use crate::time::UtcTime;
-use crate::utils::urlencode_binary;
+use crate::utils::{base64_encode, check_bucket_name, urlencode_binary};
 use crate::Minio;
 
 /// Operating presigned
 impl Minio {
-    /// Get presigned URL of an object for HTTP method, expiry time and custom request parameters.
-    /// # param
-    /// - method: HTTP method.
-    /// - bucket_name: Name of the bucket.
-    /// - object_name: Object name in the bucket.
-    /// - expires: Expiry in seconds. between 1, 604800
-    /// - response_headers Optional response_headers argument to specify response fields like date, size, type of file, data about server, etc.
-    /// - request_date: Optional request_date argument to specify a different request date. Default is current date.
-    /// - version_id: Version ID of the object.
-    /// - extra_query_params: Extra query parameters for advanced usage.
-    async fn _get_presigned_url<T1: Into<String>, T2: Into<String>>(
-        &self,
-        method: Method,
-        bucket_name: T1,
-        object_name: T2,
-        expires: usize,
-        response_headers: Option<HeaderMap>,
-        request_date: Option<UtcTime>,
-        version_id: Option<String>,
-        extra_query_params: Option<QueryMap>,
-        presigned_endpoint: Option<url::Url>,
-    ) -> Result<String> {
-        if expires < 1 || expires > 604800 {
+    /// Get presigned URL for HTTP method, expiry time and custom request parameters.
+    ///
+    /// Works with any method, on an object or on the bucket itself, so presigned `DELETE`,
+    /// `HEAD`, `ListObjects` or `UploadPart` URLs can be handed out.
+    /// Headers in [PresignedArgs::signed_header] are signed besides `host`,
+    /// the client must send them with the same values.
+    /// ## Example
+    /// ``` rust
+    /// # use minio_rsc::Minio;
+    /// # use minio_rsc::client::PresignedArgs;
+    /// use hyper::Method;
+    /// # async fn example(minio: Minio){
+    /// let list_objects: String = minio
+    ///     .presign(
+    ///         Method::GET,
+    ///         PresignedArgs::for_bucket("bucket")
+    ///             .query("list-type", "2")
+    ///             .query("prefix", "photos/")
+    ///             .expires(3600),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// let put_object: String = minio
+    ///     .presign(
+    ///         Method::PUT,
+    ///         PresignedArgs::new("bucket", "file.txt").signed_header("content-type", "text/plain"),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn presign(&self, method: Method, args: PresignedArgs) -> Result<String> {
+        if let Some(e) = args.build_err {
+            return Err(ValueError::new(e).into());
+        }
+        if args.expires < 1 || args.expires > 604800 {
             return Err(ValueError::from("expires must be between 1 second to 7 days").into());
         }
-        let date: UtcTime = request_date.unwrap_or(UtcTime::default());
-        let mut query = extra_query_params.unwrap_or(QueryMap::new());
-        if let Some(id) = version_id {
+        check_bucket_name(&args.bucket_name)?;
+        let date: UtcTime = args.request_date.unwrap_or_else(|| self.server_time());
+        let mut query = args.querys;
+        if let Some(id) = args.version_id {
             query.insert("versionId".to_string(), id);
         }
-        let credentials = self.fetch_credentials().await;
+        let credentials = self.fetch_credentials().await?;
         if let Some(token) = credentials.session_token() {
             query.insert("X-Amz-Security-Token".to_string(), token.to_string());
         }
-        if let Some(headers) = response_headers {
+        if let Some(headers) = args.headers {
             for (name, value) in &headers {
                 query.insert(name.to_string(), urlencode_binary(value.as_bytes(), false));
             }
         }
-        let uri = if let Some(presigned_endpoint) = presigned_endpoint {
-            self._build_presigned_uri(
-                Some(bucket_name.into()),
-                Some(object_name.into()),
-                presigned_endpoint,
-            )
+        let virtual_bucket = Some(args.bucket_name.clone()).filter(|_| self.virtual_hosted());
+        let uri = if let Some(presigned_endpoint) = args.presigned_endpoint {
+            self._build_presigned_uri(Some(args.bucket_name), args.object_name, presigned_endpoint)
         } else {
-            self._build_uri(Some(bucket_name.into()), Some(object_name.into()))
+            self._build_uri(Some(args.bucket_name), args.object_name)
         };
         let uri = uri + "?" + &query.to_query_string();
         let uri = Uri::from_str(&uri).map_err(|e| ValueError::new(e.to_string()))?;
-        let r = presign_v4(
-            &method,
-            &uri,
-            self.region(),
-            credentials.access_key(),
-            credentials.secret_key(),
-            &date,
-            expires,
-        );
+        let r = match self.signature_version() {
+            SignatureVersion::V4 => presign_v4_with_headers(
+                &method,
+                &uri,
+                &args.signed_headers,
+                self.region(),
+                credentials.access_key(),
+                credentials.secret_key(),
+                &date,
+                args.expires,
+            ),
+            SignatureVersion::V4A => presign_v4a(
+                &method,
+                &uri,
+                &args.signed_headers,
+                self.region_set(),
+                credentials.access_key(),
+                credentials.secret_key(),
+                &date,
+                args.expires,
+            ),
+            SignatureVersion::V2 => presign_v2(
+                &method,
+                &uri,
+                &args.signed_headers,
+                virtual_bucket.as_deref(),
+                credentials.access_key(),
+                credentials.secret_key(),
+                &date,
+                args.expires,
+            ),
+        };
         Ok(r)
     }
 
@@ -91,18 +130,7 @@ impl Minio {
     /// # }
     /// ```
     pub async fn presigned_get_object(&self, args: PresignedArgs) -> Result<String> {
-        self._get_presigned_url(
-            Method::GET,
-            args.bucket_name,
-            args.object_name,
-            args.expires,
-            args.headers,
-            args.request_date,
-            args.version_id,
-            Some(args.querys),
-            args.presigned_endpoint,
-        )
-        .await
+        self.presign(Method::GET, args).await
     }
 
     /// Get presigned URL of an object to upload data with expiry time.
@@ -122,17 +150,170 @@ impl Minio {
     /// # }
     /// ```
     pub async fn presigned_put_object(&self, args: PresignedArgs) -> Result<String> {
-        self._get_presigned_url(
-            Method::PUT,
-            args.bucket_name,
-            args.object_name,
-            args.expires,
-            args.headers,
-            args.request_date,
-            args.version_id,
-            Some(args.querys),
-            args.presigned_endpoint,
-        )
-        .await
+        self.presign(Method::PUT, args).await
+    }
+
+    /// Get the form fields of a post policy, used by browsers to upload data with HTML forms.
+    ///
+    /// Returns the upload url and the form fields, the form must be posted to the url with
+    /// all fields and the file as the last field named `file`.
+    /// ## Example
+    /// ``` rust
+    /// # use minio_rsc::Minio;
+    /// # use minio_rsc::client::PostPolicy;
+    /// # use minio_rsc::time::UtcTime;
+    /// # async fn example(minio: Minio){
+    /// let expiration = UtcTime::new(chrono::Utc::now() + chrono::Duration::days(1));
+    /// let (url, form_data) = minio
+    ///     .presigned_post_policy(
+    ///         PostPolicy::new("bucket", expiration)
+    ///             .key_starts_with("uploads/")
+    ///             .content_length_range(1, 10 * 1024 * 1024)
+    ///             .success_action_status(201),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn presigned_post_policy(
+        &self,
+        policy: PostPolicy,
+    ) -> Result<(String, HashMap<String, String>)> {
+        check_bucket_name(&policy.bucket_name)?;
+        if !policy.conditions.iter().any(|(_, e, _)| e == "key") {
+            return Err(ValueError::from("key condition must be set").into());
+        }
+        let credentials = self.fetch_credentials().await?;
+        let date = self.server_time();
+        let region = self.region();
+        let credential = format!(
+            "{}/{}/{}/s3/aws4_request",
+            credentials.access_key(),
+            date.aws_format_date(),
+            region
+        );
+
+        let mut form_data = HashMap::new();
+        let mut conditions = vec![json!(["eq", "$bucket", policy.bucket_name])];
+        for (op, element, value) in policy.conditions {
+            conditions.push(json!([op, format!("${element}"), value]));
+            if op == "eq" {
+                form_data.insert(element, value);
+            }
+        }
+        if let Some((min, max)) = policy.content_length_range {
+            if min > max {
+                return Err(
+                    ValueError::from("content length range min is greater than max").into(),
+                );
+            }
+            conditions.push(json!(["content-length-range", min, max]));
+        }
+        form_data.insert(
+            "x-amz-algorithm".to_string(),
+            "AWS4-HMAC-SHA256".to_string(),
+        );
+        form_data.insert("x-amz-credential".to_string(), credential);
+        form_data.insert("x-amz-date".to_string(), date.aws_format_time());
+        if let Some(token) = credentials.session_token() {
+            form_data.insert("x-amz-security-token".to_string(), token.to_string());
+        }
+        for name in [
+            "x-amz-algorithm",
+            "x-amz-credential",
+            "x-amz-date",
+            "x-amz-security-token",
+        ] {
+            if let Some(value) = form_data.get(name) {
+                conditions.push(json!(["eq", format!("${name}"), value]));
+            }
+        }
+
+        let document = json!({
+            "expiration": policy.expiration.format_time(),
+            "conditions": conditions,
+        });
+        let encoded = base64_encode(document.to_string());
+        let signature = post_presign_v4(&encoded, credentials.secret_key(), &date, region);
+        form_data.insert("policy".to_string(), encoded);
+        form_data.insert("x-amz-signature".to_string(), signature);
+
+        Ok((self._build_uri(Some(policy.bucket_name), None), form_data))
+    }
+
+    /// Get presigned `UploadPart` URLs of a multipart upload, one per part.
+    ///
+    /// The url at index `i` uploads part number `i + 1`.
+    /// If the task uses SSE-C, the client must send the headers of [MultipartUploadTask::ssec_header].
+    /// Once all parts are uploaded, call [Minio::complete_presigned_upload_parts] with the reported ETags.
+    /// ## Example
+    /// ``` rust
+    /// # use minio_rsc::Minio;
+    /// # async fn example(minio: Minio){
+    /// let task = minio.create_multipart_upload("bucket", "big-file.bin").await.unwrap();
+    /// let urls: Vec<String> = minio
+    ///     .presigned_upload_parts(&task, 20, 24 * 3600)
+    ///     .await
+    ///     .unwrap();
+    /// // the client uploads each part and reports back the ETag response headers.
+    /// let e_tags: Vec<String> = vec![];
+    /// minio.complete_presigned_upload_parts(&task, e_tags).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn presigned_upload_parts(
+        &self,
+        task: &MultipartUploadTask,
+        part_count: usize,
+        expires: usize,
+    ) -> Result<Vec<String>> {
+        if !(1..=MAX_MULTIPART_COUNT).contains(&part_count) {
+            return Err(ValueError::from(
+                "part_count is a positive integer between 1 and 10,000.",
+            ))?;
+        }
+        let mut signed_headers = task.ssec_header().cloned().unwrap_or_default();
+        if let Some(bucket) = task.bucket_owner() {
+            signed_headers.insert("x-amz-expected-bucket-owner", bucket.parse()?);
+        }
+        let date = self.server_time();
+        let mut urls = Vec::with_capacity(part_count);
+        for part_number in 1..=part_count {
+            let args = PresignedArgs::new(task.bucket(), task.key())
+                .query("uploadId", task.upload_id())
+                .query("partNumber", part_number.to_string())
+                .signed_headers(signed_headers.clone())
+                .regirequest_date(date)
+                .expires(expires);
+            urls.push(self.presign(Method::PUT, args).await?);
+        }
+        Ok(urls)
+    }
+
+    /// Completes a multipart upload from the ETags reported by the client,
+    /// see [Minio::presigned_upload_parts].
+    ///
+    /// `e_tags[i]` is the ETag of part number `i + 1`.
+    pub async fn complete_presigned_upload_parts(
+        &self,
+        task: &MultipartUploadTask,
+        e_tags: Vec<String>,
+    ) -> Result<CompleteMultipartUploadResult> {
+        if !(1..=MAX_MULTIPART_COUNT).contains(&e_tags.len()) {
+            return Err(ValueError::from(
+                "the number of parts must be between 1 and 10,000.",
+            ))?;
+        }
+        if e_tags
+            .iter()
+            .any(|e_tag| e_tag.trim_matches('"').is_empty())
+        {
+            return Err(ValueError::from("ETag of part cannot be empty."))?;
+        }
+        let parts = e_tags
+            .into_iter()
+            .enumerate()
+            .map(|(i, e_tag)| Part::new(i + 1, e_tag))
+            .collect();
+        self.complete_multipart_upload(task, parts, None).await
     }
 }
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use hyper::Method;
+
+/// The outcome of an S3 operation, passed to [MetricsRecorder::record].
+#[derive(Debug, Clone)]
+pub struct RequestMetrics<'a> {
+    /// The name of the operation, like `put_object`, see [BaseExecutor::operation](super::BaseExecutor::operation).
+    pub operation: &'a str,
+    pub bucket: Option<&'a str>,
+    pub method: &'a Method,
+    /// The HTTP status of the last response, None if no response was received.
+    pub status: Option<u16>,
+    /// The code of the failure, None on success:
+    /// - the [S3Error](crate::error::S3Error) code, like `NoSuchKey`.
+    /// - the HTTP status, like `404`, of an error response without body.
+    /// - `Timeout`, `HttpError` or `ClientError` if no response was received.
+    pub error_code: Option<&'a str>,
+    /// The time from sending the first attempt to the last response, including retries.
+    pub latency: Duration,
+    /// The length of the request body.
+    pub bytes_sent: u64,
+    /// The number of bytes read from the response body.
+    pub bytes_received: u64,
+    pub retries: u32,
+}
+
+/// Records the metrics of every S3 operation sent by [Minio](super::Minio),
+/// set by [MinioBuilder::metrics_recorder](super::MinioBuilder::metrics_recorder).
+///
+/// The metrics are recorded once the response body is read to the end, or dropped.
+///
+/// With the `metrics` feature, [MetricsCrateRecorder] forwards them to the `metrics` crate.
+/// ## Example
+/// ```rust
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use minio_rsc::client::{MetricsRecorder, RequestMetrics};
+/// use minio_rsc::provider::StaticProvider;
+/// use minio_rsc::Minio;
+///
+/// #[derive(Default)]
+/// struct ErrorCounter(AtomicU64);
+///
+/// impl MetricsRecorder for ErrorCounter {
+///     fn record(&self, metrics: &RequestMetrics) {
+///         if metrics.error_code.is_some() {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(StaticProvider::new("minio-access-key-test", "minio-secret-key-test", None))
+///     .metrics_recorder(ErrorCounter::default())
+///     .build()
+///     .unwrap();
+/// ```
+pub trait MetricsRecorder: Send + Sync {
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+/// A [MetricsRecorder] forwarding to the global recorder of the `metrics` crate,
+/// like a Prometheus or OpenTelemetry exporter.
+///
+/// Metrics are labelled by `operation` and `bucket`, empty for operations without bucket:
+/// - `minio_requests_total`: counter of operations, also labelled by `status`.
+/// - `minio_request_errors_total`: counter of failed operations, also labelled by `code`.
+/// - `minio_request_duration_seconds`: histogram of the latency.
+/// - `minio_request_retries_total`: counter of retries.
+/// - `minio_bytes_sent_total` and `minio_bytes_received_total`: counters of body bytes.
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[derive(Debug, Clone, Default)]
+pub struct MetricsCrateRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsCrateRecorder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsCrateRecorder {
+    fn record(&self, m: &RequestMetrics) {
+        let operation = m.operation.to_string();
+        let bucket = m.bucket.unwrap_or_default().to_string();
+        let status = m.status.map(|s| s.to_string()).unwrap_or_default();
+        let labels = [("operation", operation), ("bucket", bucket)];
+        let with = |key: &'static str, value: String| {
+            let mut labels = labels.to_vec();
+            labels.push((key, value));
+            labels
+        };
+        metrics::counter!("minio_requests_total", &with("status", status)).increment(1);
+        if let Some(code) = m.error_code {
+            metrics::counter!(
+                "minio_request_errors_total",
+                &with("code", code.to_string())
+            )
+            .increment(1);
+        }
+        metrics::histogram!("minio_request_duration_seconds", &labels)
+            .record(m.latency.as_secs_f64());
+        metrics::counter!("minio_request_retries_total", &labels).increment(m.retries as u64);
+        metrics::counter!("minio_bytes_sent_total", &labels).increment(m.bytes_sent);
+        metrics::counter!("minio_bytes_received_total", &labels).increment(m.bytes_received);
+    }
+}
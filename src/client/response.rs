@@ -1,4 +1,14 @@
 use std::collections::HashMap;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use hyper::HeaderMap;
+use reqwest::Response;
+
+use crate::checksum::Checksum;
+use crate::datatype::ChecksumAlgorithm;
+use crate::error::{Result, ValueError};
 
 #[derive(Debug, Clone)]
 pub struct ObjectStat {
@@ -10,6 +20,7 @@ pub struct ObjectStat {
     pub(crate) version_id: String,
     pub(crate) size: usize,
     pub(crate) metadata: HashMap<String, String>,
+    pub(crate) checksum: Option<(ChecksumAlgorithm, String)>,
 }
 
 impl ObjectStat {
@@ -44,4 +55,47 @@ impl ObjectStat {
     pub fn metadata(&self) -> &HashMap<String, String> {
         &self.metadata
     }
+
+    /// The algorithm and base64 encoded checksum of the object, only returned in checksum mode.
+    ///
+    /// The checksum of a multipart object ends with `-` and the number of parts.
+    pub fn checksum(&self) -> Option<&(ChecksumAlgorithm, String)> {
+        self.checksum.as_ref()
+    }
+}
+
+/// Returns the first `x-amz-checksum-*` header of a response.
+pub(crate) fn response_checksum(headers: &HeaderMap) -> Option<(ChecksumAlgorithm, String)> {
+    headers.iter().find_map(|(k, v)| {
+        let algorithm = ChecksumAlgorithm::from_header_name(k.as_str())?;
+        Some((algorithm, v.to_str().ok()?.to_string()))
+    })
+}
+
+/// Returns the body stream of a `get_object` response, verifying the checksum of the object
+/// returned in checksum mode as the body streams.
+///
+/// The last item is an error if the body doesn't match the checksum.
+/// The body passes through unverified if the response has no checksum,
+/// or the checksum of a multipart object, which is computed from the parts.
+pub fn checksum_verified_stream(
+    response: Response,
+) -> Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>> {
+    let checksum = response_checksum(response.headers()).filter(|(_, c)| !c.contains('-'));
+    let mut stream = response.bytes_stream();
+    Box::pin(async_stream::try_stream! {
+        let mut hasher = checksum.as_ref().map(|(algorithm, _)| Checksum::new(algorithm));
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&chunk);
+            }
+            yield chunk;
+        }
+        if let (Some(hasher), Some((_, expected))) = (hasher, checksum) {
+            if hasher.finalize() != expected {
+                Err(ValueError::from("checksum of the object mismatch"))?;
+            }
+        }
+    })
 }
@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use hyper::{http::response::Parts, Method, StatusCode};
+use reqwest::{Body, Response, ResponseBuilderExt, Url};
+
+use crate::error::{Error, Result, S3Error};
+
+/// The codes of S3 error responses worth sending the request again.
+const RETRYABLE_CODES: [&str; 10] = [
+    "InternalError",
+    "RequestTimeout",
+    "RequestThrottled",
+    "ServiceUnavailable",
+    "SlowDown",
+    "Throttling",
+    "ThrottlingException",
+    "XMinioServerNotInitialized",
+    "XMinioReadQuorum",
+    "XMinioWriteQuorum",
+];
+
+/// How failed requests are retried, with exponential backoff and jitter.
+///
+/// A request is sent again if the S3 service is throttling or unavailable,
+/// see [RetryPolicy::is_retryable_status] and [RetryPolicy::is_retryable_code],
+/// or if the connection failed, was reset or timed out, see [RetryPolicy::is_retryable_error].
+///
+/// `POST` requests are not idempotent and only sent once,
+/// unless enabled by [RetryPolicy::retry_post].
+///
+/// The delay before the n-th retry is `base_delay * 2^(n-1)`, capped at `max_delay`.
+/// With jitter, a random delay between zero and that is taken instead.
+///
+/// [Data::Bytes](crate::Data::Bytes) bodies are sent again as is,
+/// a [Data::Stream](crate::Data::Stream) body is only sent once,
+/// unless it is set by [BaseExecutor::rewindable_body](super::BaseExecutor::rewindable_body).
+///
+/// Default: 3 attempts, 100ms base delay, 5s max delay, with jitter.
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use minio_rsc::client::RetryPolicy;
+/// use minio_rsc::provider::StaticProvider;
+/// use minio_rsc::Minio;
+///
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(StaticProvider::new("minio-access-key-test", "minio-secret-key-test", None))
+///     .retry_policy(
+///         RetryPolicy::new()
+///             .max_attempts(5)
+///             .base_delay(Duration::from_millis(50))
+///             .max_delay(Duration::from_secs(2)),
+///     )
+///     .build()
+///     .unwrap();
+/// assert_eq!(minio.retry_policy().get_max_attempts(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_post: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+            retry_post: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy sending every request only once.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set the maximum number of times a request is sent, including the first attempt.
+    ///
+    /// Values below 1 are taken as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry, doubled for each later retry.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the upper bound of the delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set whether to randomize the delay, spreading out the retries of concurrent requests.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set whether to retry `POST` requests too, like `CompleteMultipartUpload` and `DeleteObjects`.
+    ///
+    /// Default: false, the service may have applied a `POST` before the connection failed.
+    pub fn retry_post(mut self, retry_post: bool) -> Self {
+        self.retry_post = retry_post;
+        self
+    }
+
+    /// The maximum number of times a request is sent.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The delay before the `retry`-th retry, starting at 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if self.jitter {
+            delay.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+
+    /// Whether a request of the method may be sent again, every method but `POST`,
+    /// see [RetryPolicy::retry_post].
+    pub fn is_retryable_method(&self, method: &Method) -> bool {
+        self.retry_post || method != Method::POST
+    }
+
+    /// Whether a response of the HTTP status is worth retrying,
+    /// `408`, `429`, `500`, `502`, `503` and `504`.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::REQUEST_TIMEOUT
+                | StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Whether an S3 error response of the code is worth retrying, like `SlowDown`.
+    pub fn is_retryable_code(code: &str) -> bool {
+        RETRYABLE_CODES.contains(&code)
+    }
+
+    /// Whether the request failed for a transient reason,
+    /// a connection failure or reset, a timeout, or a retryable S3 error response.
+    ///
+    /// A connection reset while sending the request or reading the response
+    /// is reported by reqwest as a request or body error.
+    pub fn is_retryable_error(err: &Error) -> bool {
+        match err.inner() {
+            Error::HttpError(e) => {
+                e.is_connect() || e.is_timeout() || e.is_request() || e.is_body()
+            }
+            Error::S3Error(e) => Self::is_retryable_code(&e.code),
+            Error::UnknownResponse(res) => Self::is_retryable_status(res.status()),
+            _ => false,
+        }
+    }
+}
+
+/// Read the code of an S3 error response.
+///
+/// Returns a response with the same status, headers, url, extensions and body,
+/// as the body is consumed.
+pub(crate) async fn peek_error_code(res: Response) -> Result<(Response, Option<String>)> {
+    let (res, body) = peek_error_body(res).await?;
+    let code = S3Error::try_from(body.as_ref()).ok().map(|e| e.code);
+    Ok((res, code))
+}
+
+/// Read the body of an error response, see [peek_error_code].
+pub(crate) async fn peek_error_body(res: Response) -> Result<(Response, Bytes)> {
+    let url = res.url().clone();
+    let (parts, body) = hyper::Response::<Body>::from(res).into_parts();
+    let body: Bytes = body.collect().await?.to_bytes();
+    Ok((response_from_parts(parts, body.clone(), url), body))
+}
+
+/// Build a response from the parts of another one, with a new body.
+pub(crate) fn response_from_parts<B: Into<Body>>(mut parts: Parts, body: B, url: Url) -> Response {
+    // `Response::from` takes the url from the extensions.
+    let mut builder = hyper::Response::builder().url(url);
+    if let Some(extensions) = builder.extensions_mut() {
+        parts.extensions.extend(std::mem::take(extensions));
+    }
+    hyper::Response::from_parts(parts, body).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::{Method, StatusCode};
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1))
+            .jitter(false);
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(4), Duration::from_millis(800));
+        assert_eq!(policy.delay(5), Duration::from_secs(1));
+        assert_eq!(policy.delay(100), Duration::from_secs(1));
+
+        let policy = policy.jitter(true);
+        for retry in 1..10 {
+            assert!(policy.delay(retry) <= Duration::from_secs(1));
+        }
+        assert_eq!(RetryPolicy::none().get_max_attempts(), 1);
+        assert_eq!(RetryPolicy::new().max_attempts(0).get_max_attempts(), 1);
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::FORBIDDEN));
+        assert!(RetryPolicy::is_retryable_code("SlowDown"));
+        assert!(RetryPolicy::is_retryable_code("RequestTimeout"));
+        assert!(!RetryPolicy::is_retryable_code("NoSuchKey"));
+
+        let policy = RetryPolicy::new();
+        assert!(policy.is_retryable_method(&Method::PUT));
+        assert!(policy.is_retryable_method(&Method::DELETE));
+        assert!(!policy.is_retryable_method(&Method::POST));
+        assert!(policy.retry_post(true).is_retryable_method(&Method::POST));
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use futures::Future;
+use http_body_util::BodyExt;
+use hyper::{HeaderMap, Method, StatusCode, Uri};
+use reqwest::{Body, Response, ResponseBuilderExt, Url};
+
+use crate::error::{Error, Result};
+
+pub type ResponseFuture = Pin<Box<dyn Future<Output = Result<Response>> + Send>>;
+
+/// Sends the signed HTTP requests of [Minio](super::Minio),
+/// set by [MinioBuilder::transport](super::MinioBuilder::transport).
+///
+/// Implemented by [reqwest::Client], the default, and [MockTransport].
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, method: Method, uri: String, headers: HeaderMap, body: Body) -> ResponseFuture;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn send(&self, method: Method, uri: String, headers: HeaderMap, body: Body) -> ResponseFuture {
+        let request = self.request(method, uri).headers(headers).body(body);
+        Box::pin(async move { Ok(request.send().await?) })
+    }
+}
+
+/// A canned response of [MockTransport].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl MockResponse {
+    pub fn new(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// A `200 OK` response.
+    pub fn ok() -> Self {
+        Self::new(200)
+    }
+
+    /// An S3 error response of the code, like `NoSuchKey`.
+    pub fn s3_error(status: u16, code: &str, message: &str) -> Self {
+        Self::new(status)
+            .header("content-type", "application/xml")
+            .body(format!(
+                "<Error><Code>{code}</Code><Message>{message}</Message><RequestId>mock</RequestId></Error>"
+            ))
+    }
+
+    /// Set a response header.
+    ///
+    /// # Panics
+    /// If the name or the value is not a valid header.
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        let name: hyper::header::HeaderName = key
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid mock header name {:?}", key));
+        let value: hyper::header::HeaderValue = value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid mock value of header {}", key));
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Set the response body.
+    pub fn body<B: Into<Bytes>>(mut self, body: B) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    fn to_response(&self, url: Option<Url>) -> Response {
+        let mut builder = hyper::Response::builder().status(self.status);
+        if let Some(url) = url {
+            builder = builder.url(url);
+        }
+        let mut response = builder.body(self.body.clone()).unwrap();
+        *response.headers_mut() = self.headers.clone();
+        response.into()
+    }
+}
+
+/// A request received by [MockTransport].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub uri: Uri,
+    pub headers: HeaderMap,
+    /// The body as sent, aws-chunked payloads are not decoded.
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// The path of the request uri, like `/bucket/key`.
+    pub fn path(&self) -> &str {
+        self.uri.path()
+    }
+
+    /// The value of the header, if it is valid ASCII.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).and_then(|v| v.to_str().ok())
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Vec<(Method, String, VecDeque<MockResponse>)>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// An in-memory [HttpTransport] answering with canned responses, to test S3 flows offline.
+///
+/// Responses are registered per method and path of the request uri, the query is ignored.
+/// Responses registered on the same route are returned in order, the last one repeats.
+/// A request matching no route is answered by a `404 NotFound` S3 error.
+///
+/// Clones share the routes and the recorded requests.
+/// ## Example
+/// ```rust
+/// use bytes::Bytes;
+/// use hyper::Method;
+/// use minio_rsc::client::{MockResponse, MockTransport};
+/// use minio_rsc::provider::StaticProvider;
+/// use minio_rsc::Minio;
+///
+/// # async fn example() -> minio_rsc::error::Result<()> {
+/// let mock = MockTransport::new();
+/// mock.on(Method::PUT, "/bucket/hello.txt", MockResponse::ok().header("etag", "\"1\""));
+/// mock.on(
+///     Method::GET,
+///     "/bucket/hello.txt",
+///     MockResponse::ok().body("Hello world"),
+/// );
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(StaticProvider::new("minio-access-key-test", "minio-secret-key-test", None))
+///     .transport(mock.clone())
+///     .build()
+///     .unwrap();
+/// minio.put_object("bucket", "hello.txt", Bytes::from("Hello world")).await?;
+/// let text = minio.get_object("bucket", "hello.txt").await?.text().await?;
+/// assert_eq!(text, "Hello world");
+/// assert_eq!(mock.requests()[0].body, "Hello world");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a response to requests of the method and path, like `/bucket/key`.
+    pub fn on<P: Into<String>>(&self, method: Method, path: P, response: MockResponse) {
+        let path = path.into();
+        let mut state = self.state.lock().unwrap();
+        match state
+            .routes
+            .iter_mut()
+            .find(|(m, p, _)| m == method && p == &path)
+        {
+            Some((_, _, responses)) => responses.push_back(response),
+            None => state
+                .routes
+                .push((method, path, VecDeque::from([response]))),
+        }
+    }
+
+    /// The requests received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Forget the requests received so far.
+    pub fn clear_requests(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+
+    fn respond(&self, request: RecordedRequest) -> Response {
+        let mut state = self.state.lock().unwrap();
+        let response = state
+            .routes
+            .iter_mut()
+            .find(|(m, p, _)| m == request.method && p == request.path())
+            .and_then(|(_, _, responses)| {
+                if responses.len() > 1 {
+                    responses.pop_front()
+                } else {
+                    responses.front().cloned()
+                }
+            })
+            .unwrap_or_else(|| {
+                let message = format!("no mock response for {} {}", request.method, request.path());
+                MockResponse::s3_error(404, "NotFound", &message)
+            });
+        let url = Url::parse(&request.uri.to_string()).ok();
+        state.requests.push(request);
+        response.to_response(url)
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, method: Method, uri: String, headers: HeaderMap, body: Body) -> ResponseFuture {
+        let mock = self.clone();
+        Box::pin(async move {
+            let uri: Uri = uri.parse().map_err(|_| Error::ValueError(uri.clone()))?;
+            let body = body.collect().await?.to_bytes();
+            Ok(mock.respond(RecordedRequest {
+                method,
+                uri,
+                headers,
+                body,
+            }))
+        })
+    }
+}
@@ -39,13 +39,23 @@ impl Credentials {
         self.session_token.as_ref()
     }
 
+    /// Get expiration, unix timestamp in seconds.
+    pub fn expiration(&self) -> Option<i64> {
+        self.expiration
+    }
+
     /// Check whether this credentials expired or not.
     pub fn is_expired(&self) -> bool {
-        if let Some(exp) = self.expiration {
-            let now = UtcTime::now();
-            now.before(exp - 10)
-        } else {
-            false
+        self.expires_within(10)
+    }
+
+    /// Check whether this credentials expire within `seconds` from now.
+    ///
+    /// Credentials without expiration never expire.
+    pub fn expires_within(&self, seconds: i64) -> bool {
+        match self.expiration {
+            Some(exp) => UtcTime::now().before(exp - seconds),
+            None => false,
         }
     }
 }
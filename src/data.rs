@@ -19,6 +19,9 @@ pub(crate) enum PayloadHash {
     /// Use this when sending a payload over multiple chunks, and the chunks are signed using `AWS4-HMAC-SHA256`. This produces a SigV4 signature.
     /// In addition, the digest for the chunks is included as a trailing header.
     StreamingTrailer,
+    /// Use this when sending an unsigned payload over multiple chunks.
+    /// In this case, you have a trailing header after the chunk is uploaded.
+    StreamingUnsignedTrailer,
     EmptySha256,
 }
 
@@ -34,6 +37,7 @@ impl PayloadHash {
             PayloadHash::Unsigned => "UNSIGNED-PAYLOAD",
             PayloadHash::Streaming => "STREAMING-AWS4-HMAC-SHA256-PAYLOAD",
             PayloadHash::StreamingTrailer => "STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER",
+            PayloadHash::StreamingUnsignedTrailer => "STREAMING-UNSIGNED-PAYLOAD-TRAILER",
         }
     }
 }
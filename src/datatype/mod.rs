@@ -40,6 +40,8 @@ impl_xmlself!(
     LocationConstraint
     PublicAccessBlockConfiguration
     AccessControlPolicy
+    AssumeRoleResponse
+    StsErrorResponse
 );
 
 pub trait ToXml {
@@ -91,6 +93,38 @@ pub struct AccessControlPolicy {
     pub owner: Option<Owner>,
 }
 
+/// Response of the STS `AssumeRole`, `AssumeRoleWithWebIdentity`, `AssumeRoleWithClientGrants`
+/// and `AssumeRoleWithLDAPIdentity` APIs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssumeRoleResponse {
+    #[serde(
+        alias = "AssumeRoleWithWebIdentityResult",
+        alias = "AssumeRoleWithClientGrantsResult",
+        alias = "AssumeRoleWithLDAPIdentityResult"
+    )]
+    pub assume_role_result: AssumeRoleResult,
+    pub response_metadata: Option<ResponseMetadata>,
+}
+
+/// Contains the temporary security credentials returned by STS.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssumeRoleResult {
+    pub credentials: StsCredentials,
+    pub assumed_role_user: Option<AssumedRoleUser>,
+}
+
+/// The identifiers for the temporary security credentials that the operation returns.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssumedRoleUser {
+    #[serde(default)]
+    pub arn: String,
+    #[serde(default)]
+    pub assumed_role_id: String,
+}
+
 /// In terms of implementation, a Bucket is a resource.
 /// An Amazon S3 bucket name is globally unique, and the namespace is shared by all AWS accounts.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -126,11 +160,36 @@ pub struct CompleteMultipartUpload {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
 pub struct CompleteMultipartUploadResult {
     pub bucket: String,
     pub key: String,
     pub e_tag: String,
     pub location: String,
+    #[serde(
+        rename = "ChecksumCRC32",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32: Option<String>,
+    #[serde(
+        rename = "ChecksumCRC32C",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32c: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA1",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha1: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA256",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha256: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -426,11 +485,69 @@ pub struct Owner {
     pub id: String,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// A part of a multipart upload, passed to `complete_multipart_upload`.
+///
+/// More fields may be added, create it with [Part::new].
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
 pub struct Part {
     pub e_tag: String,
     pub part_number: usize,
+    #[serde(
+        rename = "ChecksumCRC32",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32: Option<String>,
+    #[serde(
+        rename = "ChecksumCRC32C",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_crc32c: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA1",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha1: Option<String>,
+    #[serde(
+        rename = "ChecksumSHA256",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checksum_sha256: Option<String>,
+}
+
+impl Part {
+    pub fn new<T: Into<String>>(part_number: usize, e_tag: T) -> Self {
+        Self {
+            e_tag: e_tag.into(),
+            part_number,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the base64 encoded checksum of the part computed with the algorithm.
+    pub fn checksum(&self, algorithm: &ChecksumAlgorithm) -> Option<&String> {
+        match algorithm {
+            ChecksumAlgorithm::CRC32 => self.checksum_crc32.as_ref(),
+            ChecksumAlgorithm::CRC32C => self.checksum_crc32c.as_ref(),
+            ChecksumAlgorithm::SHA1 => self.checksum_sha1.as_ref(),
+            ChecksumAlgorithm::SHA256 => self.checksum_sha256.as_ref(),
+        }
+    }
+
+    /// Set the base64 encoded checksum of the part computed with the algorithm.
+    pub fn set_checksum(&mut self, algorithm: &ChecksumAlgorithm, checksum: Option<String>) {
+        match algorithm {
+            ChecksumAlgorithm::CRC32 => self.checksum_crc32 = checksum,
+            ChecksumAlgorithm::CRC32C => self.checksum_crc32c = checksum,
+            ChecksumAlgorithm::SHA1 => self.checksum_sha1 = checksum,
+            ChecksumAlgorithm::SHA256 => self.checksum_sha256 = checksum,
+        }
+    }
 }
 
 /// This data type contains information about progress of an operation.
@@ -470,6 +587,13 @@ pub struct ReplicationRule {
     pub priority: Option<i64>,
 }
 
+/// Contains the request id of STS APIs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResponseMetadata {
+    pub request_id: String,
+}
+
 /// Object representation of request XML of `put_object_retention` API
 /// and response XML of `get_object_retention` API.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -518,6 +642,37 @@ pub struct Stats {
     pub bytes_scanned: u64,
 }
 
+/// Temporary security credentials returned by STS.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    /// The date on which the current credentials expire.
+    #[serde(deserialize_with = "crate::time::deserialize_with_str")]
+    pub expiration: UtcTime,
+}
+
+/// Error detail of STS APIs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StsError {
+    #[serde(default)]
+    pub r#type: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// STS service returned error response.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StsErrorResponse {
+    pub error: StsError,
+    #[serde(default)]
+    pub request_id: String,
+}
+
 /// A container of a key value name pair.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -560,6 +715,7 @@ pub struct VersioningConfiguration {
 
 //////////////////  Enum Type
 
+/// The algorithm used to create the checksum of the object.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum ChecksumAlgorithm {
     CRC32,
@@ -568,6 +724,39 @@ pub enum ChecksumAlgorithm {
     SHA256,
 }
 
+impl ChecksumAlgorithm {
+    /// The value of the `x-amz-checksum-algorithm` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::CRC32 => "CRC32",
+            ChecksumAlgorithm::CRC32C => "CRC32C",
+            ChecksumAlgorithm::SHA1 => "SHA1",
+            ChecksumAlgorithm::SHA256 => "SHA256",
+        }
+    }
+
+    /// The header carrying the base64 encoded checksum, like `x-amz-checksum-crc32`.
+    pub fn header_name(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::CRC32 => "x-amz-checksum-crc32",
+            ChecksumAlgorithm::CRC32C => "x-amz-checksum-crc32c",
+            ChecksumAlgorithm::SHA1 => "x-amz-checksum-sha1",
+            ChecksumAlgorithm::SHA256 => "x-amz-checksum-sha256",
+        }
+    }
+
+    /// Returns the algorithm of a `x-amz-checksum-*` header name, ignoring case.
+    pub fn from_header_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "x-amz-checksum-crc32" => Some(ChecksumAlgorithm::CRC32),
+            "x-amz-checksum-crc32c" => Some(ChecksumAlgorithm::CRC32C),
+            "x-amz-checksum-sha1" => Some(ChecksumAlgorithm::SHA1),
+            "x-amz-checksum-sha256" => Some(ChecksumAlgorithm::SHA256),
+            _ => None,
+        }
+    }
+}
+
 /// Type of grantee
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum GranteeType {
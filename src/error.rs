@@ -5,6 +5,7 @@ use hyper::{
     Error as RequestError,
 };
 use serde::Deserialize;
+use std::sync::Arc;
 use std::{convert::Infallible, error::Error as StdError};
 use std::{fmt::Display, result};
 
@@ -109,6 +110,52 @@ impl TryFrom<&str> for S3Error {
     }
 }
 
+/// A request failed the signature verification of [crate::verify_request_v4].
+///
+/// Each variant maps to the code of the S3 error response, see [SignatureError::code].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The authorization header or the presign query parameters are missing or malformed.
+    AuthorizationMalformed(String),
+    /// The access key is unknown to the secret key lookup.
+    InvalidAccessKeyId(String),
+    /// The calculated signature does not match the signature of the request.
+    SignatureDoesNotMatch,
+    /// The request time is outside the allowed clock skew.
+    RequestTimeTooSkewed,
+    /// The presigned url has expired.
+    RequestExpired,
+}
+
+impl SignatureError {
+    /// The code of the S3 error response.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SignatureError::AuthorizationMalformed(_) => "AuthorizationHeaderMalformed",
+            SignatureError::InvalidAccessKeyId(_) => "InvalidAccessKeyId",
+            SignatureError::SignatureDoesNotMatch => "SignatureDoesNotMatch",
+            SignatureError::RequestTimeTooSkewed => "RequestTimeTooSkewed",
+            SignatureError::RequestExpired => "AccessDenied",
+        }
+    }
+}
+
+impl Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::AuthorizationMalformed(e) => {
+                write!(f, "authorization malformed: {}", e)
+            }
+            SignatureError::InvalidAccessKeyId(ak) => write!(f, "invalid access key id: {}", ak),
+            SignatureError::SignatureDoesNotMatch => write!(f, "signature does not match"),
+            SignatureError::RequestTimeTooSkewed => write!(f, "request time too skewed"),
+            SignatureError::RequestExpired => write!(f, "request has expired"),
+        }
+    }
+}
+
+impl StdError for SignatureError {}
+
 /// InternalException - thrown to indicate internal library error.
 /// ErrorResponseException - thrown to indicate S3 service returned an error response.
 /// thrown to indicate I/O error on S3 operation.
@@ -141,6 +188,19 @@ pub enum Error {
 
     /// indicate I/O error, had on S3 operation.
     IoError(std::io::Error),
+
+    /// indicate an error shared by several callers, like a failed credentials refresh.
+    Shared(Arc<Error>),
+}
+
+impl Error {
+    /// The error itself, or the error wrapped by [Error::Shared].
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Shared(e) => e.inner(),
+            e => e,
+        }
+    }
 }
 
 impl StdError for Error {
@@ -148,6 +208,7 @@ impl StdError for Error {
         match self {
             Error::RequestError(e) => e.source(),
             Error::S3Error(e) => e.source(),
+            Error::Shared(e) => Some(e.as_ref()),
             _ => None,
         }
     }
@@ -166,6 +227,7 @@ impl fmt::Display for Error {
             Error::MessageDecodeError(e)=> write!(f, "{}", e),
             Error::SelectObejectError(e)=> write!(f, "{}", e),
             Error::IoError(e) => write!(f, "{}", e),
+            Error::Shared(e) => write!(f, "{}", e),
         }
     }
 }
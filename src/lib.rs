@@ -1,5 +1,6 @@
 #![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+mod checksum;
 pub mod client;
 mod credentials;
 mod data;
@@ -15,4 +16,9 @@ pub mod xml;
 pub use crate::client::Minio;
 pub use crate::credentials::Credentials;
 pub use crate::data::Data;
-pub use crate::signer::{presign_v4, sign_request_v4, sign_v4_authorization};
+pub use crate::signer::{
+    post_presign_v4, presign_v2, presign_v4, presign_v4_with_headers, presign_v4a, sign_request_v2,
+    sign_request_v4, sign_request_v4a, sign_v2_authorization, sign_v4_authorization,
+    sign_v4a_authorization, verify_presign_v4, verify_request_v4, verify_v4_authorization,
+    SignatureVersion, VerifiedSignature, MAX_CLOCK_SKEW,
+};
@@ -0,0 +1,143 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures::future::{FutureExt, Shared};
+
+use super::{CredentialFuture, Provider};
+use crate::error::Error;
+use crate::Credentials;
+
+/// Default seconds before expiration at which cached credentials are refreshed.
+pub(crate) const REFRESH_BEFORE_EXPIRY: i64 = 60;
+
+type SharedFetch =
+    Shared<Pin<Box<dyn Future<Output = std::result::Result<Credentials, Arc<Error>>> + Send>>>;
+
+#[derive(Default)]
+struct CacheState {
+    credentials: Option<Credentials>,
+    inflight: Option<SharedFetch>,
+}
+
+/// Credentials cache shared by the clones of a provider.
+///
+/// Concurrent refreshes are deduplicated into a single in-flight fetch.
+#[derive(Clone)]
+pub(crate) struct CredentialCache {
+    refresh_before: i64,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl Default for CredentialCache {
+    fn default() -> Self {
+        Self::new(REFRESH_BEFORE_EXPIRY)
+    }
+}
+
+impl CredentialCache {
+    pub(crate) fn new(refresh_before: i64) -> Self {
+        Self {
+            refresh_before,
+            state: Default::default(),
+        }
+    }
+
+    pub(crate) fn set(&self, credentials: Credentials) {
+        self.state.lock().unwrap().credentials = Some(credentials);
+    }
+
+    /// Returns the cached credentials, or join the in-flight `refresh`, starting it if needed.
+    ///
+    /// When several callers share a failed refresh, the last one to finish receives the original
+    /// error, the others an [Error::Shared] wrapping it.
+    pub(crate) fn fetch<F>(&self, refresh: F) -> CredentialFuture
+    where
+        F: FnOnce() -> CredentialFuture,
+    {
+        let inflight = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(credentials) = state
+                .credentials
+                .as_ref()
+                .filter(|c| !c.expires_within(self.refresh_before))
+            {
+                let credentials = credentials.clone();
+                return Box::pin(async move { Ok(credentials) });
+            }
+            match &state.inflight {
+                Some(inflight) => inflight.clone(),
+                None => {
+                    let inflight = refresh().map(|r| r.map_err(Arc::new)).boxed().shared();
+                    state.inflight = Some(inflight.clone());
+                    inflight
+                }
+            }
+        };
+        let state = self.state.clone();
+        Box::pin(async move {
+            let result = inflight.clone().await;
+            {
+                let mut state = state.lock().unwrap();
+                if state.inflight.as_ref().is_some_and(|f| f.ptr_eq(&inflight)) {
+                    state.inflight = None;
+                    if let Ok(credentials) = &result {
+                        state.credentials = Some(credentials.clone());
+                    }
+                }
+            }
+            drop(inflight);
+            result.map_err(|e| Arc::try_unwrap(e).unwrap_or_else(Error::Shared))
+        })
+    }
+}
+
+/// Wraps a provider, caches the credentials it returns
+/// and refreshes them ahead of their expiration.
+///
+/// Concurrent refreshes share a single in-flight fetch of the wrapped provider,
+/// refresh failures are returned to the callers.
+/// Credentials without expiration are cached forever.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::{CachingProvider, EnvMinioProvider};
+///
+/// let provider = CachingProvider::new(EnvMinioProvider).refresh_before(300);
+/// ```
+pub struct CachingProvider<P> {
+    provider: Arc<P>,
+    cache: CredentialCache,
+}
+
+impl<P> Clone for CachingProvider<P> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<P: Provider> CachingProvider<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            cache: Default::default(),
+        }
+    }
+
+    /// Set how many seconds before expiration the credentials are refreshed.
+    ///
+    /// Default: `60`.
+    pub fn refresh_before(mut self, seconds: i64) -> Self {
+        self.cache = CredentialCache::new(seconds);
+        self
+    }
+}
+
+impl<P: Provider + 'static> Provider for CachingProvider<P> {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.provider.clone();
+        self.cache.fetch(move || provider.fetch())
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use super::{
+    AwsFileProvider, CachingProvider, CredentialFuture, EcsProvider, EnvAwsProvider,
+    EnvMinioProvider, ImdsProvider, McConfigProvider, Provider, WebIdentityProvider,
+};
+use crate::error::Error;
+
+/// Tries an ordered list of providers,
+/// returns the credentials of the first one that succeeds.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::{ChainProvider, EnvMinioProvider, StaticProvider};
+///
+/// let provider = ChainProvider::new()
+///     .push(EnvMinioProvider)
+///     .push(StaticProvider::new("minio-access-key-test", "minio-secret-key-test", None));
+/// ```
+#[derive(Clone, Default)]
+pub struct ChainProvider(Vec<Arc<dyn Provider>>);
+
+impl ChainProvider {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Append a provider to the end of the chain.
+    pub fn push<P>(mut self, provider: P) -> Self
+    where
+        P: Provider + 'static,
+    {
+        self.0.push(Arc::new(provider));
+        self
+    }
+}
+
+impl Provider for ChainProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let providers = self.0.clone();
+        Box::pin(async move {
+            let mut errors = Vec::new();
+            for provider in providers {
+                match provider.fetch().await {
+                    Ok(cred) => return Ok(cred),
+                    Err(e) => errors.push(e.to_string()),
+                }
+            }
+            Err(Error::ValueError(format!(
+                "No credentials found in chain: [{}]",
+                errors.join("; ")
+            )))
+        })
+    }
+}
+
+/// Resolves credentials in the standard order:
+/// 1. MinIO environment variables, [EnvMinioProvider].
+/// 2. AWS environment variables, [EnvAwsProvider].
+/// 3. AWS shared credentials and config file, [AwsFileProvider].
+/// 4. The `local` alias of the MinIO client (mc) config file, [McConfigProvider].
+/// 5. STS web identity from `AWS_WEB_IDENTITY_TOKEN_FILE`, [WebIdentityProvider::from_env].
+/// 6. ECS/EKS container credential endpoint, [EcsProvider::from_env].
+/// 7. EC2 instance metadata service, [ImdsProvider], unless `AWS_EC2_METADATA_DISABLED` is `true`.
+///
+/// The resolved credentials are cached by a [CachingProvider], so the chain, and the
+/// metadata service timeout off EC2, are only walked again when they are about to expire.
+/// Credentials without expiration, like environment variables, are resolved once.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::DefaultProvider;
+/// use minio_rsc::Minio;
+///
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(DefaultProvider::new())
+///     .secure(false)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct DefaultProvider(CachingProvider<ChainProvider>);
+
+impl DefaultProvider {
+    pub fn new() -> Self {
+        let mut chain = ChainProvider::new()
+            .push(EnvMinioProvider)
+            .push(EnvAwsProvider)
+            .push(AwsFileProvider::new())
+            .push(McConfigProvider::new("local"));
+        if let Some(provider) = WebIdentityProvider::from_env() {
+            chain = chain.push(provider);
+        }
+        if let Some(provider) = EcsProvider::from_env() {
+            chain = chain.push(provider);
+        }
+        let imds_disabled = std::env::var("AWS_EC2_METADATA_DISABLED")
+            .is_ok_and(|v| v.eq_ignore_ascii_case("true"));
+        if !imds_disabled {
+            chain = chain.push(ImdsProvider::new());
+        }
+        Self(CachingProvider::new(chain))
+    }
+}
+
+impl Default for DefaultProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Provider for DefaultProvider {
+    fn fetch(&self) -> CredentialFuture {
+        self.0.fetch()
+    }
+}
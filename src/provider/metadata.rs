@@ -0,0 +1,291 @@
+use std::env;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::cache::CredentialCache;
+use super::{read_token_file, CredentialFuture, Provider};
+use crate::error::{Error, Result, ValueError};
+use crate::time::UtcTime;
+use crate::Credentials;
+
+const IMDS_ENDPOINT: &str = "http://169.254.169.254";
+const ECS_ENDPOINT: &str = "http://169.254.170.2";
+const EKS_IPV4: Ipv4Addr = Ipv4Addr::new(169, 254, 170, 23);
+const EKS_IPV6: Ipv6Addr = Ipv6Addr::new(0xfd00, 0xec2, 0, 0, 0, 0, 0, 0x23);
+
+/// Credentials document returned by the instance metadata service and the container endpoint.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetadataCredentials {
+    #[serde(default)]
+    code: Option<String>,
+    access_key_id: String,
+    secret_access_key: String,
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(deserialize_with = "crate::time::deserialize_with_str")]
+    expiration: UtcTime,
+}
+
+impl MetadataCredentials {
+    fn parse(text: &str) -> std::result::Result<Credentials, ValueError> {
+        let c: Self = serde_json::from_str(text).map_err(|e| ValueError::new(e.to_string()))?;
+        match c.code.as_deref() {
+            None | Some("Success") => Ok(Credentials::new(
+                c.access_key_id,
+                c.secret_access_key,
+                c.token,
+                Some(c.expiration.timestamp()),
+            )),
+            Some(code) => Err(ValueError::new(format!(
+                "metadata credentials not available: {}",
+                code
+            ))),
+        }
+    }
+}
+
+/// Send the request, returns the body text of a successful response.
+async fn response_text(request: reqwest::RequestBuilder) -> Result<String> {
+    let res = request.send().await?;
+    if res.status().is_success() {
+        Ok(res.text().await?)
+    } else {
+        Err(Error::UnknownResponse(res))
+    }
+}
+
+/// The custom http client, or a client with short timeouts, the metadata endpoints are link-local.
+fn metadata_client(client: &Option<reqwest::Client>) -> reqwest::Result<reqwest::Client> {
+    match client {
+        Some(client) => Ok(client.clone()),
+        None => reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(1))
+            .timeout(Duration::from_secs(5))
+            .build(),
+    }
+}
+
+/// Whether credentials may be requested from the url of `AWS_CONTAINER_CREDENTIALS_FULL_URI`,
+/// like the AWS SDKs: any https url, or http on a loopback host
+/// or the ECS and EKS link-local endpoints.
+fn is_allowed_full_uri(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    match (url.scheme(), url.host()) {
+        ("https", Some(_)) => true,
+        ("http", Some(url::Host::Domain(domain))) => domain.eq_ignore_ascii_case("localhost"),
+        ("http", Some(url::Host::Ipv4(ip))) => {
+            ip.is_loopback() || ip == Ipv4Addr::new(169, 254, 170, 2) || ip == EKS_IPV4
+        }
+        ("http", Some(url::Host::Ipv6(ip))) => ip.is_loopback() || ip == EKS_IPV6,
+        _ => false,
+    }
+}
+
+/// Retrieves the credentials of the IAM role attached to an EC2 instance,
+/// from the instance metadata service with the IMDSv2 session token flow.
+///
+/// endpoint: `AWS_EC2_METADATA_SERVICE_ENDPOINT`, default `http://169.254.169.254`.
+///
+/// The returned credentials are cached and refreshed before they expire.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::ImdsProvider;
+///
+/// let provider = ImdsProvider::new().endpoint("http://127.0.0.1:1338");
+/// ```
+#[derive(Clone)]
+pub struct ImdsProvider {
+    endpoint: String,
+    token_ttl: usize,
+    role_name: Option<String>,
+    client: Option<reqwest::Client>,
+    cache: CredentialCache,
+}
+
+impl ImdsProvider {
+    pub fn new() -> Self {
+        Self {
+            endpoint: env::var("AWS_EC2_METADATA_SERVICE_ENDPOINT")
+                .unwrap_or_else(|_| IMDS_ENDPOINT.to_string()),
+            token_ttl: 21600,
+            role_name: None,
+            client: None,
+            cache: Default::default(),
+        }
+    }
+
+    /// Set the endpoint of the instance metadata service.
+    pub fn endpoint<T: Into<String>>(mut self, endpoint: T) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// Set the lifetime, in seconds, of the IMDSv2 session token.
+    ///
+    /// Default: `21600`.
+    pub fn token_ttl(mut self, seconds: usize) -> Self {
+        self.token_ttl = seconds;
+        self
+    }
+
+    /// Set the name of the IAM role, by default the role attached to the instance is looked up.
+    pub fn role_name<T: Into<String>>(mut self, role_name: T) -> Self {
+        self.role_name = Some(role_name.into());
+        self
+    }
+
+    /// Set custom http [reqwest::Client].
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Request new credentials, bypassing the cache.
+    pub async fn retrieve(&self) -> Result<Credentials> {
+        let client = metadata_client(&self.client)?;
+        let endpoint = self.endpoint.trim_end_matches('/');
+        let token = response_text(
+            client
+                .put(format!("{}/latest/api/token", endpoint))
+                .header("x-aws-ec2-metadata-token-ttl-seconds", self.token_ttl),
+        )
+        .await?;
+        let url = format!("{}/latest/meta-data/iam/security-credentials/", endpoint);
+        let role_name = match &self.role_name {
+            Some(role_name) => role_name.clone(),
+            None => response_text(client.get(&url).header("x-aws-ec2-metadata-token", &token))
+                .await?
+                .lines()
+                .next()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .ok_or_else(|| ValueError::from("no IAM role attached to the instance"))?,
+        };
+        let text = response_text(
+            client
+                .get(format!("{}{}", url, role_name))
+                .header("x-aws-ec2-metadata-token", &token),
+        )
+        .await?;
+        let credentials = MetadataCredentials::parse(&text)?;
+        self.cache.set(credentials.clone());
+        Ok(credentials)
+    }
+}
+
+impl Default for ImdsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Provider for ImdsProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.clone();
+        self.cache
+            .fetch(move || Box::pin(async move { provider.retrieve().await }))
+    }
+}
+
+/// Retrieves the credentials of an ECS task role or EKS pod identity,
+/// from the container credential endpoint.
+///
+/// The returned credentials are cached and refreshed before they expire.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::EcsProvider;
+///
+/// let provider = EcsProvider::new("http://127.0.0.1:1338/v2/credentials")
+///     .authorization_token("Basic YWJjOmRlZg==");
+/// ```
+#[derive(Clone)]
+pub struct EcsProvider {
+    url: String,
+    authorization_token: Option<String>,
+    authorization_token_file: Option<PathBuf>,
+    client: Option<reqwest::Client>,
+    cache: CredentialCache,
+}
+
+impl EcsProvider {
+    /// - url: full url of the credential endpoint.
+    pub fn new<T: Into<String>>(url: T) -> Self {
+        Self {
+            url: url.into(),
+            authorization_token: None,
+            authorization_token_file: None,
+            client: None,
+            cache: Default::default(),
+        }
+    }
+
+    /// load configuration from AWS environment variables.
+    /// - `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`, relative to `http://169.254.170.2`
+    /// - `AWS_CONTAINER_CREDENTIALS_FULL_URI`, an https url,
+    ///   or an http url on a loopback host or the ECS and EKS link-local endpoints.
+    /// - `AWS_CONTAINER_AUTHORIZATION_TOKEN`
+    /// - `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`
+    ///
+    /// Returns `None` if neither uri is set, or the full uri is not allowed.
+    pub fn from_env() -> Option<Self> {
+        let url = match env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+            Ok(uri) => format!("{}{}", ECS_ENDPOINT, uri),
+            Err(_) => env::var("AWS_CONTAINER_CREDENTIALS_FULL_URI")
+                .ok()
+                .filter(|url| is_allowed_full_uri(url))?,
+        };
+        let mut provider = Self::new(url);
+        provider.authorization_token = env::var("AWS_CONTAINER_AUTHORIZATION_TOKEN").ok();
+        provider.authorization_token_file =
+            env::var_os("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE").map(PathBuf::from);
+        Some(provider)
+    }
+
+    /// Set the value of the `Authorization` header.
+    pub fn authorization_token<T: Into<String>>(mut self, token: T) -> Self {
+        self.authorization_token = Some(token.into());
+        self
+    }
+
+    /// Read the value of the `Authorization` header from a file on every refresh,
+    /// takes precedence over [authorization_token](Self::authorization_token).
+    pub fn authorization_token_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.authorization_token_file = Some(path.into());
+        self
+    }
+
+    /// Set custom http [reqwest::Client].
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Request new credentials, bypassing the cache.
+    pub async fn retrieve(&self) -> Result<Credentials> {
+        let token = match &self.authorization_token_file {
+            Some(path) => Some(read_token_file(path.clone()).await?),
+            None => self.authorization_token.clone(),
+        };
+        let mut request = metadata_client(&self.client)?.get(&self.url);
+        if let Some(token) = token {
+            request = request.header(reqwest::header::AUTHORIZATION, token);
+        }
+        let credentials = MetadataCredentials::parse(&response_text(request).await?)?;
+        self.cache.set(credentials.clone());
+        Ok(credentials)
+    }
+}
+
+impl Provider for EcsProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.clone();
+        self.cache
+            .fetch(move || Box::pin(async move { provider.retrieve().await }))
+    }
+}
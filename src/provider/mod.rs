@@ -0,0 +1,122 @@
+//! Credential provider
+mod cache;
+mod chain;
+mod file;
+mod metadata;
+mod sts;
+
+use futures::Future;
+use std::{env, pin::Pin};
+
+use crate::error::{Error, Result};
+use crate::Credentials;
+
+pub use cache::CachingProvider;
+pub use chain::{ChainProvider, DefaultProvider};
+pub use file::{AwsFileProvider, AwsProfile, McAlias, McConfigProvider};
+pub use metadata::{EcsProvider, ImdsProvider};
+pub use sts::{AssumeRoleProvider, LdapIdentityProvider, WebIdentityProvider};
+
+pub type CredentialFuture = Pin<Box<dyn Future<Output = Result<Credentials>> + Send>>;
+
+/// define Credential retriever.
+pub trait Provider: Send + Sync {
+    fn fetch(&self) -> CredentialFuture;
+}
+
+#[derive(Debug, Clone)]
+pub struct StaticProvider(Credentials);
+
+impl StaticProvider {
+    pub fn new<T: Into<String>>(ak: T, sk: T, st: Option<String>) -> Self {
+        Self(Credentials::new(ak, sk, st, None))
+    }
+
+    /// load Credentials from MinIO environment variables.
+    /// - `MINIO_ACCESS_KEY`
+    /// - `MINIO_SECRET_KEY`
+    /// - `MINIO_SESSION_TOKEN`
+    pub fn from_env() -> Option<Self> {
+        if let (Ok(ak), Ok(sk), st) = (
+            env::var("MINIO_ACCESS_KEY"),
+            env::var("MINIO_SECRET_KEY"),
+            env::var("MINIO_SESSION_TOKEN"),
+        ) {
+            Some(Self::new(ak, sk, st.ok()))
+        } else {
+            None
+        }
+    }
+
+    /// load Credentials from AWS environment variables.
+    /// - `AWS_ACCESS_KEY_ID` or `AWS_ACCESS_KEY`
+    /// - `AWS_SECRET_ACCESS_KEY` or `AWS_SECRET_KEY`
+    /// - `AWS_SESSION_TOKEN`
+    pub fn from_env_aws() -> Option<Self> {
+        let ak = env::var("AWS_ACCESS_KEY_ID").or_else(|_| env::var("AWS_ACCESS_KEY"));
+        let sk = env::var("AWS_SECRET_ACCESS_KEY").or_else(|_| env::var("AWS_SECRET_KEY"));
+        if let (Ok(ak), Ok(sk), st) = (ak, sk, env::var("AWS_SESSION_TOKEN")) {
+            Some(Self::new(ak, sk, st.ok()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Provider for StaticProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let cred = self.0.clone();
+        Box::pin(async move { Ok(cred) })
+    }
+}
+
+/// Reads Credentials from MinIO environment variables on every fetch,
+/// see [StaticProvider::from_env].
+#[derive(Debug, Clone, Default)]
+pub struct EnvMinioProvider;
+
+impl Provider for EnvMinioProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let cred = StaticProvider::from_env()
+            .map(|p| p.0)
+            .ok_or_else(|| Error::ValueError("Miss MinIO environment credentials".to_string()));
+        Box::pin(async move { cred })
+    }
+}
+
+/// Reads Credentials from AWS environment variables on every fetch,
+/// see [StaticProvider::from_env_aws].
+#[derive(Debug, Clone, Default)]
+pub struct EnvAwsProvider;
+
+impl Provider for EnvAwsProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let cred = StaticProvider::from_env_aws()
+            .map(|p| p.0)
+            .ok_or_else(|| Error::ValueError("Miss AWS environment credentials".to_string()));
+        Box::pin(async move { cred })
+    }
+}
+
+/// Read a file without blocking the async runtime.
+///
+/// Uses `tokio::fs` with the `fs-tokio` feature, otherwise reads it on a separate thread.
+pub(crate) async fn read_file(path: std::path::PathBuf) -> Result<String> {
+    #[cfg(feature = "fs-tokio")]
+    let text = tokio::fs::read_to_string(path).await?;
+    #[cfg(not(feature = "fs-tokio"))]
+    let text = {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(std::fs::read_to_string(path));
+        });
+        rx.await
+            .map_err(|_| Error::ValueError("file reader stopped".to_string()))??
+    };
+    Ok(text)
+}
+
+/// Read a token file without blocking the async runtime, trimmed of whitespace.
+pub(crate) async fn read_token_file(path: std::path::PathBuf) -> Result<String> {
+    Ok(read_file(path).await?.trim().to_string())
+}
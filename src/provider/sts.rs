@@ -0,0 +1,519 @@
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use hyper::{header, HeaderMap, Method, Uri};
+
+use super::cache::CredentialCache;
+use super::{read_token_file, CredentialFuture, Provider};
+use crate::client::{response_from_parts, QueryMap};
+use crate::datatype::{AssumeRoleResponse, FromXml, StsCredentials, StsErrorResponse};
+use crate::error::{Error, Result, S3Error, ValueError};
+use crate::signer::{sha256_hash, sign_v4_authorization};
+use crate::time::UtcTime;
+use crate::Credentials;
+
+const STS_API_VERSION: &str = "2011-06-15";
+
+impl From<StsCredentials> for Credentials {
+    fn from(c: StsCredentials) -> Self {
+        Credentials::new(
+            c.access_key_id,
+            c.secret_access_key,
+            c.session_token,
+            Some(c.expiration.timestamp()),
+        )
+    }
+}
+
+/// Send a STS request with form-encoded `querys` as body and parse the returned credentials.
+///
+/// The request is signed with `credentials` when given, otherwise it is sent unsigned.
+async fn sts_request(
+    client: &reqwest::Client,
+    endpoint: &str,
+    region: &str,
+    querys: QueryMap,
+    credentials: Option<&Credentials>,
+) -> Result<Credentials> {
+    let uri = Uri::from_str(endpoint).map_err(|e| ValueError::new(e.to_string()))?;
+    let body = Bytes::from(querys.to_query_string());
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "application/x-www-form-urlencoded".parse()?,
+    );
+    if let Some(credentials) = credentials {
+        let date = UtcTime::now();
+        let content_sha256 = sha256_hash(&body);
+        if let Some(authority) = uri.authority() {
+            headers.insert(header::HOST, authority.as_str().parse()?);
+        }
+        headers.insert("x-amz-date", date.aws_format_time().parse()?);
+        headers.insert("x-amz-content-sha256", content_sha256.parse()?);
+        if let Some(token) = credentials.session_token() {
+            headers.insert("x-amz-security-token", token.parse()?);
+        }
+        let authorization = sign_v4_authorization(
+            &Method::POST,
+            &uri,
+            region,
+            "sts",
+            &headers,
+            credentials.access_key(),
+            credentials.secret_key(),
+            &content_sha256,
+            &date,
+        );
+        headers.insert(header::AUTHORIZATION, authorization.parse()?);
+    }
+    let res = client
+        .post(endpoint)
+        .headers(headers)
+        .body(body)
+        .send()
+        .await?;
+    if res.status().is_success() {
+        let response = AssumeRoleResponse::from_xml(res.text().await?)?;
+        return Ok(response.assume_role_result.credentials.into());
+    }
+    let url = res.url().clone();
+    let (parts, body) = hyper::Response::<reqwest::Body>::from(res).into_parts();
+    let body = body.collect().await?.to_bytes();
+    match StsErrorResponse::from_xml(String::from_utf8_lossy(&body).to_string()) {
+        Ok(StsErrorResponse { error, request_id }) => Err(S3Error {
+            code: error.code,
+            message: error.message,
+            resource: String::new(),
+            request_id,
+            host_id: None,
+            bucket_name: None,
+            object_name: None,
+        })?,
+        // not a STS error, like the html page of a proxy.
+        Err(_) => Err(Error::UnknownResponse(response_from_parts(
+            parts, body, url,
+        ))),
+    }
+}
+
+/// Retrieves temporary credentials with the STS `AssumeRole` API,
+/// the request is signed with the base credentials.
+///
+/// The returned credentials are cached and refreshed before they expire.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::AssumeRoleProvider;
+/// use minio_rsc::Minio;
+///
+/// let provider = AssumeRoleProvider::new("http://localhost:9022", "minio-access-key-test", "minio-secret-key-test")
+///     .duration_seconds(3600);
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(provider)
+///     .secure(false)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct AssumeRoleProvider {
+    endpoint: String,
+    region: String,
+    credentials: Credentials,
+    role_arn: Option<String>,
+    role_session_name: Option<String>,
+    duration_seconds: Option<usize>,
+    policy: Option<String>,
+    external_id: Option<String>,
+    client: reqwest::Client,
+    cache: CredentialCache,
+}
+
+impl AssumeRoleProvider {
+    /// - endpoint: url of the STS service, like `http://localhost:9000` or `https://sts.amazonaws.com`.
+    /// - access_key, secret_key: the base credentials used to sign the `AssumeRole` request.
+    pub fn new<T1, T2, T3>(endpoint: T1, access_key: T2, secret_key: T3) -> Self
+    where
+        T1: Into<String>,
+        T2: Into<String>,
+        T3: Into<String>,
+    {
+        Self {
+            endpoint: endpoint.into(),
+            region: "us-east-1".to_string(),
+            credentials: Credentials::new(access_key, secret_key, None, None),
+            role_arn: None,
+            role_session_name: None,
+            duration_seconds: None,
+            policy: None,
+            external_id: None,
+            client: reqwest::Client::new(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Set session token of the base credentials.
+    pub fn session_token<T: Into<String>>(mut self, session_token: T) -> Self {
+        self.credentials = Credentials::new(
+            self.credentials.access_key(),
+            self.credentials.secret_key(),
+            Some(session_token.into()),
+            None,
+        );
+        self
+    }
+
+    /// Set region of the STS service.
+    ///
+    /// Default: `us-east-1`
+    pub fn region<T: Into<String>>(mut self, region: T) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    /// Set the Amazon Resource Name (ARN) of the role to assume.
+    pub fn role_arn<T: Into<String>>(mut self, role_arn: T) -> Self {
+        self.role_arn = Some(role_arn.into());
+        self
+    }
+
+    /// Set an identifier for the assumed role session.
+    pub fn role_session_name<T: Into<String>>(mut self, role_session_name: T) -> Self {
+        self.role_session_name = Some(role_session_name.into());
+        self
+    }
+
+    /// Set the duration, in seconds, of the role session. between 900, 43200
+    pub fn duration_seconds(mut self, duration_seconds: usize) -> Self {
+        self.duration_seconds = Some(duration_seconds);
+        self
+    }
+
+    /// Set an IAM policy in JSON format to further restrict the session permissions.
+    pub fn policy<T: Into<String>>(mut self, policy: T) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+
+    /// Set a unique identifier that might be required when you assume a role in another account.
+    pub fn external_id<T: Into<String>>(mut self, external_id: T) -> Self {
+        self.external_id = Some(external_id.into());
+        self
+    }
+
+    /// Set custom http [reqwest::Client].
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Request new temporary credentials, bypassing the cache.
+    pub async fn assume_role(&self) -> Result<Credentials> {
+        let mut querys = QueryMap::new();
+        querys.insert("Action".to_string(), "AssumeRole".to_string());
+        querys.insert("Version".to_string(), STS_API_VERSION.to_string());
+        if let Some(role_arn) = &self.role_arn {
+            querys.insert("RoleArn".to_string(), role_arn.clone());
+        }
+        if let Some(role_session_name) = &self.role_session_name {
+            querys.insert("RoleSessionName".to_string(), role_session_name.clone());
+        }
+        if let Some(duration_seconds) = self.duration_seconds {
+            querys.insert("DurationSeconds".to_string(), duration_seconds.to_string());
+        }
+        if let Some(policy) = &self.policy {
+            querys.insert("Policy".to_string(), policy.clone());
+        }
+        if let Some(external_id) = &self.external_id {
+            querys.insert("ExternalId".to_string(), external_id.clone());
+        }
+        let credentials = sts_request(
+            &self.client,
+            &self.endpoint,
+            &self.region,
+            querys,
+            Some(&self.credentials),
+        )
+        .await?;
+        self.cache.set(credentials.clone());
+        Ok(credentials)
+    }
+}
+
+impl Provider for AssumeRoleProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.clone();
+        self.cache
+            .fetch(move || Box::pin(async move { provider.assume_role().await }))
+    }
+}
+
+/// Source of the JWT exchanged by [WebIdentityProvider].
+#[derive(Clone)]
+enum TokenSource {
+    File(PathBuf),
+    Fn(Arc<dyn Fn() -> Result<String> + Send + Sync>),
+}
+
+/// Retrieves temporary credentials by exchanging a JWT issued by an OpenID Connect provider,
+/// through the STS `AssumeRoleWithWebIdentity` API,
+/// or the MinIO `AssumeRoleWithClientGrants` API if [client_grants](Self::client_grants) is set.
+///
+/// The token is read again on every refresh, so rotated tokens are picked up.
+/// The returned credentials are cached and refreshed before they expire.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::WebIdentityProvider;
+///
+/// let provider = WebIdentityProvider::from_file(
+///     "http://localhost:9022",
+///     "/var/run/secrets/tokens/minio",
+/// )
+/// .duration_seconds(3600);
+/// ```
+#[derive(Clone)]
+pub struct WebIdentityProvider {
+    endpoint: String,
+    token: TokenSource,
+    client_grants: bool,
+    role_arn: Option<String>,
+    role_session_name: Option<String>,
+    duration_seconds: Option<usize>,
+    policy: Option<String>,
+    client: reqwest::Client,
+    cache: CredentialCache,
+}
+
+impl WebIdentityProvider {
+    fn new<T: Into<String>>(endpoint: T, token: TokenSource) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            token,
+            client_grants: false,
+            role_arn: None,
+            role_session_name: None,
+            duration_seconds: None,
+            policy: None,
+            client: reqwest::Client::new(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Read the JWT from a file, like a Kubernetes projected service account token.
+    /// - endpoint: url of the STS service, like `http://localhost:9000` or `https://sts.amazonaws.com`.
+    pub fn from_file<T: Into<String>, P: Into<PathBuf>>(endpoint: T, path: P) -> Self {
+        Self::new(endpoint, TokenSource::File(path.into()))
+    }
+
+    /// Get the JWT from a callback.
+    /// - endpoint: url of the STS service, like `http://localhost:9000` or `https://sts.amazonaws.com`.
+    pub fn from_fn<T, F>(endpoint: T, f: F) -> Self
+    where
+        T: Into<String>,
+        F: Fn() -> Result<String> + Send + Sync + 'static,
+    {
+        Self::new(endpoint, TokenSource::Fn(Arc::new(f)))
+    }
+
+    /// load configuration from AWS environment variables.
+    /// - `AWS_WEB_IDENTITY_TOKEN_FILE`
+    /// - `AWS_ROLE_ARN`
+    /// - `AWS_ROLE_SESSION_NAME`
+    /// - `AWS_ENDPOINT_URL_STS`, default `https://sts.amazonaws.com`
+    pub fn from_env() -> Option<Self> {
+        let path = env::var("AWS_WEB_IDENTITY_TOKEN_FILE").ok()?;
+        let endpoint = env::var("AWS_ENDPOINT_URL_STS")
+            .unwrap_or_else(|_| "https://sts.amazonaws.com".to_string());
+        let mut provider = Self::from_file(endpoint, path);
+        provider.role_arn = env::var("AWS_ROLE_ARN").ok();
+        provider.role_session_name = env::var("AWS_ROLE_SESSION_NAME").ok();
+        Some(provider)
+    }
+
+    /// Set flag to use the MinIO `AssumeRoleWithClientGrants` API
+    /// instead of `AssumeRoleWithWebIdentity`.
+    ///
+    /// Default: `false`.
+    pub fn client_grants(mut self, client_grants: bool) -> Self {
+        self.client_grants = client_grants;
+        self
+    }
+
+    /// Set the Amazon Resource Name (ARN) of the role to assume.
+    pub fn role_arn<T: Into<String>>(mut self, role_arn: T) -> Self {
+        self.role_arn = Some(role_arn.into());
+        self
+    }
+
+    /// Set an identifier for the assumed role session.
+    pub fn role_session_name<T: Into<String>>(mut self, role_session_name: T) -> Self {
+        self.role_session_name = Some(role_session_name.into());
+        self
+    }
+
+    /// Set the duration, in seconds, of the role session. between 900, 43200
+    pub fn duration_seconds(mut self, duration_seconds: usize) -> Self {
+        self.duration_seconds = Some(duration_seconds);
+        self
+    }
+
+    /// Set an IAM policy in JSON format to further restrict the session permissions.
+    pub fn policy<T: Into<String>>(mut self, policy: T) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+
+    /// Set custom http [reqwest::Client].
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Exchange the JWT for new temporary credentials, bypassing the cache.
+    pub async fn assume_role(&self) -> Result<Credentials> {
+        let token = match &self.token {
+            TokenSource::File(path) => read_token_file(path.clone()).await?,
+            TokenSource::Fn(f) => f()?,
+        };
+        if token.is_empty() {
+            return Err(Error::ValueError("web identity token is empty".to_string()));
+        }
+        let mut querys = QueryMap::new();
+        if self.client_grants {
+            querys.insert(
+                "Action".to_string(),
+                "AssumeRoleWithClientGrants".to_string(),
+            );
+            querys.insert("Token".to_string(), token);
+        } else {
+            querys.insert(
+                "Action".to_string(),
+                "AssumeRoleWithWebIdentity".to_string(),
+            );
+            querys.insert("WebIdentityToken".to_string(), token);
+        }
+        querys.insert("Version".to_string(), STS_API_VERSION.to_string());
+        if let Some(role_arn) = &self.role_arn {
+            querys.insert("RoleArn".to_string(), role_arn.clone());
+        }
+        if let Some(role_session_name) = &self.role_session_name {
+            querys.insert("RoleSessionName".to_string(), role_session_name.clone());
+        }
+        if let Some(duration_seconds) = self.duration_seconds {
+            querys.insert("DurationSeconds".to_string(), duration_seconds.to_string());
+        }
+        if let Some(policy) = &self.policy {
+            querys.insert("Policy".to_string(), policy.clone());
+        }
+        let credentials = sts_request(&self.client, &self.endpoint, "", querys, None).await?;
+        self.cache.set(credentials.clone());
+        Ok(credentials)
+    }
+}
+
+impl Provider for WebIdentityProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.clone();
+        self.cache
+            .fetch(move || Box::pin(async move { provider.assume_role().await }))
+    }
+}
+
+/// Retrieves temporary credentials for an LDAP user,
+/// through the MinIO `AssumeRoleWithLDAPIdentity` API.
+///
+/// The returned credentials are cached and refreshed before they expire.
+/// ## Example
+/// ```rust
+/// use minio_rsc::provider::LdapIdentityProvider;
+/// use minio_rsc::Minio;
+///
+/// let provider = LdapIdentityProvider::new("http://localhost:9022", "ldap-user", "ldap-password");
+/// let minio = Minio::builder()
+///     .endpoint("localhost:9022")
+///     .provider(provider)
+///     .secure(false)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct LdapIdentityProvider {
+    endpoint: String,
+    username: String,
+    password: String,
+    duration_seconds: Option<usize>,
+    policy: Option<String>,
+    client: reqwest::Client,
+    cache: CredentialCache,
+}
+
+impl LdapIdentityProvider {
+    /// - endpoint: url of the MinIO server, like `http://localhost:9000`.
+    /// - username, password: the LDAP credentials.
+    pub fn new<T1, T2, T3>(endpoint: T1, username: T2, password: T3) -> Self
+    where
+        T1: Into<String>,
+        T2: Into<String>,
+        T3: Into<String>,
+    {
+        Self {
+            endpoint: endpoint.into(),
+            username: username.into(),
+            password: password.into(),
+            duration_seconds: None,
+            policy: None,
+            client: reqwest::Client::new(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Set the duration, in seconds, of the credentials. between 900, 43200
+    pub fn duration_seconds(mut self, duration_seconds: usize) -> Self {
+        self.duration_seconds = Some(duration_seconds);
+        self
+    }
+
+    /// Set an IAM policy in JSON format to further restrict the session permissions.
+    pub fn policy<T: Into<String>>(mut self, policy: T) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+
+    /// Set custom http [reqwest::Client].
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Request new temporary credentials, bypassing the cache.
+    pub async fn assume_role(&self) -> Result<Credentials> {
+        let mut querys = QueryMap::new();
+        querys.insert(
+            "Action".to_string(),
+            "AssumeRoleWithLDAPIdentity".to_string(),
+        );
+        querys.insert("Version".to_string(), STS_API_VERSION.to_string());
+        querys.insert("LDAPUsername".to_string(), self.username.clone());
+        querys.insert("LDAPPassword".to_string(), self.password.clone());
+        if let Some(duration_seconds) = self.duration_seconds {
+            querys.insert("DurationSeconds".to_string(), duration_seconds.to_string());
+        }
+        if let Some(policy) = &self.policy {
+            querys.insert("Policy".to_string(), policy.clone());
+        }
+        let credentials = sts_request(&self.client, &self.endpoint, "", querys, None).await?;
+        self.cache.set(credentials.clone());
+        Ok(credentials)
+    }
+}
+
+impl Provider for LdapIdentityProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let provider = self.clone();
+        self.cache
+            .fetch(move || Box::pin(async move { provider.assume_role().await }))
+    }
+}
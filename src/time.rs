@@ -1,5 +1,5 @@
 //! Time formatter for S3 APIs.
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// wrap of `chrono::Utc`
@@ -18,6 +18,18 @@ impl UtcTime {
         Self::new(Utc::now())
     }
 
+    /// Returns the number of non-leap seconds since January 1, 1970 0:00:00 UTC
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Returns the time moved by the given number of seconds.
+    #[inline]
+    pub fn add_seconds(&self, seconds: i64) -> Self {
+        Self::new(self.0 + chrono::Duration::seconds(seconds))
+    }
+
     #[inline]
     pub(crate) fn before(&self, timestamp: i64) -> bool {
         timestamp < self.0.timestamp()
@@ -44,6 +56,28 @@ impl UtcTime {
     pub fn aws_format_date(&self) -> String {
         self.0.format("%Y%m%d").to_string()
     }
+
+    /// format date to RFC 7231, like`Sun, 10 Sep 2023 08:26:43 GMT`
+    ///
+    /// Used in the `Date` header.
+    #[inline]
+    pub fn http_format_time(&self) -> String {
+        self.0.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    }
+
+    /// parse date from ISO8601 like`20230910T082643Z`, the format of [UtcTime::aws_format_time].
+    pub fn from_aws_format_time(s: &str) -> Option<Self> {
+        NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ")
+            .ok()
+            .map(|t| Self::new(t.and_utc()))
+    }
+
+    /// parse date from RFC 7231 like`Sun, 10 Sep 2023 08:26:43 GMT`, the format of the `Date` header.
+    pub fn from_http_format_time(s: &str) -> Option<Self> {
+        DateTime::parse_from_rfc2822(s)
+            .ok()
+            .map(|t| Self::new(t.with_timezone(&Utc)))
+    }
 }
 
 impl From<DateTime<Utc>> for UtcTime {
@@ -4,10 +4,11 @@ mod test {
     use serde::Deserialize;
 
     use crate::datatype::{
-        AccessControlPolicy, CompleteMultipartUploadResult, CopyPartResult,
-        InitiateMultipartUploadResult, LegalHold, ListAllMyBucketsResult, ListBucketResult,
-        ListMultipartUploadsResult, ListPartsResult, ListVersionsResult, ObjectLockConfiguration,
-        Retention, Tagging, VersioningConfiguration,
+        AccessControlPolicy, AssumeRoleResponse, AssumedRoleUser, ChecksumAlgorithm,
+        CompleteMultipartUploadResult, CopyPartResult, InitiateMultipartUploadResult, LegalHold,
+        ListAllMyBucketsResult, ListBucketResult, ListMultipartUploadsResult, ListPartsResult,
+        ListVersionsResult, ObjectLockConfiguration, Part, Retention, StsErrorResponse, Tagging,
+        VersioningConfiguration,
     };
 
     macro_rules! test_datatypes {
@@ -384,6 +385,70 @@ mod test {
         "#
     );
 
+    test_datatypes!(
+        AssumeRoleResponse,
+        test_assume_role_response,
+        r#"
+        <AssumeRoleResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+            <AssumeRoleResult>
+                <AssumedRoleUser>
+                    <Arn>arn:aws:sts::123456789012:assumed-role/demo/TestAR</Arn>
+                    <AssumedRoleId>ARO123EXAMPLE123:TestAR</AssumedRoleId>
+                </AssumedRoleUser>
+                <Credentials>
+                    <AccessKeyId>Y4RJU1RNFGK48LGO9I2S</AccessKeyId>
+                    <SecretAccessKey>sYLRKS1Z7hSjluf6gEbb9066hnx315wHTiACPAjg</SecretAccessKey>
+                    <Expiration>2019-08-08T20:26:12Z</Expiration>
+                    <SessionToken>eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9</SessionToken>
+                </Credentials>
+            </AssumeRoleResult>
+            <ResponseMetadata>
+                <RequestId>c6104cbe-af31-11e0-8154-cbc7ccf896c7</RequestId>
+            </ResponseMetadata>
+        </AssumeRoleResponse>
+        "#
+    );
+
+    test_datatypes!(
+        StsErrorResponse,
+        test_sts_error_response,
+        r#"
+        <ErrorResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+            <Error>
+                <Type></Type>
+                <Code>InvalidClientTokenId</Code>
+                <Message>The security token included in the request is invalid.</Message>
+            </Error>
+            <RequestId>17B2E7E1F8DF9C1A</RequestId>
+        </ErrorResponse>
+        "#
+    );
+
+    #[test]
+    fn test_assumed_role_user() {
+        let txt = r#"<AssumedRoleUser>
+            <Arn>arn:aws:sts::123456789012:assumed-role/demo/TestAR</Arn>
+            <AssumedRoleId>ARO123EXAMPLE123:TestAR</AssumedRoleId>
+        </AssumedRoleUser>"#;
+        let user = crate::xml::de::from_str::<AssumedRoleUser>(txt).unwrap();
+        assert_eq!(
+            user.arn,
+            "arn:aws:sts::123456789012:assumed-role/demo/TestAR"
+        );
+        assert_eq!(user.assumed_role_id, "ARO123EXAMPLE123:TestAR");
+    }
+
+    #[test]
+    fn test_part_new() {
+        let mut part = Part::new(1, "\"etag\"");
+        let xml = crate::xml::ser::to_string(&part).unwrap();
+        assert!(!xml.contains("Checksum"));
+        part.set_checksum(&ChecksumAlgorithm::CRC32, Some("AAAAAA==".to_string()));
+        let xml = crate::xml::ser::to_string(&part).unwrap();
+        assert!(xml.contains("<ChecksumCRC32>AAAAAA==</ChecksumCRC32>"));
+        assert!(xml.contains("<PartNumber>1</PartNumber>"));
+    }
+
     #[test]
     fn test_struct() {
         #[derive(Deserialize, PartialEq, Debug)]
@@ -0,0 +1,156 @@
+//! A local HTTP stand-in server, for tests that don't need a MinIO server.
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A request received by the stand-in server.
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub type Requests = Arc<Mutex<Vec<Recorded>>>;
+
+/// Start a local HTTP stand-in, every request is answered with `respond`.
+///
+/// Returns the endpoint url and the requests received.
+pub async fn stand_in<F>(respond: F) -> (String, Requests)
+where
+    F: Fn(&Recorded) -> (u16, String) + Send + Sync + 'static,
+{
+    stand_in_with_headers(move |r| {
+        let (status, body) = respond(r);
+        (status, vec![], body)
+    })
+    .await
+}
+
+/// Start a local HTTP stand-in, `respond` also returns the response headers.
+pub async fn stand_in_with_headers<F>(respond: F) -> (String, Requests)
+where
+    F: Fn(&Recorded) -> (u16, Vec<(String, String)>, String) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let requests: Requests = Default::default();
+    let recorder = requests.clone();
+    let respond = Arc::new(respond);
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let recorder = recorder.clone();
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let head_end = loop {
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break i + 4;
+                    }
+                };
+                let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+                let mut lines = head.split("\r\n");
+                let mut request_line = lines.next().unwrap().split(' ');
+                let method = request_line.next().unwrap().to_string();
+                let path = request_line.next().unwrap().to_string();
+                let headers: Vec<(String, String)> = lines
+                    .filter_map(|l| l.split_once(':'))
+                    .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+                    .collect();
+                let len: usize = headers
+                    .iter()
+                    .find(|(k, _)| k == "content-length")
+                    .map(|(_, v)| v.parse().unwrap())
+                    .unwrap_or(0);
+                let chunked = headers
+                    .iter()
+                    .any(|(k, v)| k == "transfer-encoding" && v.contains("chunked"));
+                loop {
+                    let complete = if chunked {
+                        buf[head_end..].ends_with(b"0\r\n\r\n")
+                    } else {
+                        buf.len() >= head_end + len
+                    };
+                    if complete {
+                        break;
+                    }
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+                let body = if chunked {
+                    decode_chunked(&buf[head_end..])
+                } else {
+                    buf[head_end..].to_vec()
+                };
+                let body = String::from_utf8_lossy(&body).to_string();
+                let recorded = Recorded {
+                    method,
+                    path,
+                    headers,
+                    body,
+                };
+                let (status, headers, body) = respond(&recorded);
+                recorder.lock().unwrap().push(recorded);
+                // a body is sent in one chunk if the headers ask for chunked encoding.
+                let chunked = headers
+                    .iter()
+                    .any(|(k, v)| k.eq_ignore_ascii_case("transfer-encoding") && v == "chunked");
+                let headers: String = headers
+                    .iter()
+                    .map(|(k, v)| format!("{k}: {v}\r\n"))
+                    .collect();
+                let response = if chunked {
+                    format!(
+                        "HTTP/1.1 {status} STATUS\r\n{headers}Connection: close\r\n\r\n{:x}\r\n{body}\r\n0\r\n\r\n",
+                        body.len()
+                    )
+                } else {
+                    format!(
+                        "HTTP/1.1 {status} STATUS\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            });
+        }
+    });
+    (endpoint, requests)
+}
+
+/// Decode a `Transfer-Encoding: chunked` body.
+fn decode_chunked(mut raw: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    while let Some(i) = raw.windows(2).position(|w| w == b"\r\n") {
+        let size = String::from_utf8_lossy(&raw[..i]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16).unwrap();
+        if size == 0 {
+            break;
+        }
+        body.extend_from_slice(&raw[i + 2..i + 2 + size]);
+        raw = &raw[i + 2 + size + 2..];
+    }
+    body
+}
@@ -0,0 +1,1152 @@
+mod support;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures::StreamExt;
+use hyper::{HeaderMap, Method, Uri};
+use minio_rsc::client::{
+    checksum_verified_stream, Interceptor, KeyArgs, ListObjectsArgs, MetricsRecorder, MockResponse,
+    MockTransport, PostPolicy, PresignedArgs, RequestMetrics, RetryPolicy,
+};
+use minio_rsc::datatype::ChecksumAlgorithm;
+use minio_rsc::error::{Error, Result};
+use minio_rsc::provider::StaticProvider;
+use minio_rsc::time::UtcTime;
+use minio_rsc::{post_presign_v4, Data, Minio, SignatureVersion};
+use support::{stand_in, stand_in_with_headers};
+
+fn test_minio(endpoint: &str) -> Minio {
+    Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .anonymous()
+        .secure(false)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_anonymous() -> Result<()> {
+    let (endpoint, requests) =
+        stand_in(
+            |r| match (r.method.as_str(), r.path.split('?').next().unwrap()) {
+                ("GET", "/public-bucket") => (
+                    200,
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>public-bucket</Name>
+  <Prefix></Prefix>
+  <KeyCount>1</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>hello.txt</Key>
+    <LastModified>2023-09-10T08:26:43.296Z</LastModified>
+    <ETag>"5eb63bbbe01eeed093cb22bb8f5acdc3"</ETag>
+    <Size>11</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#
+                        .to_string(),
+                ),
+                ("GET", "/public-bucket/hello.txt") => (200, "hello world".to_string()),
+                ("HEAD", "/public-bucket/hello.txt") => (200, String::new()),
+                _ => (404, String::new()),
+            },
+        )
+        .await;
+    let minio = test_minio(&endpoint);
+    assert!(minio.is_anonymous());
+
+    let result = minio
+        .list_objects("public-bucket", ListObjectsArgs::default())
+        .await?;
+    assert_eq!(result.contents.len(), 1);
+    assert_eq!(result.contents[0].key, "hello.txt");
+
+    let response = minio.get_object("public-bucket", "hello.txt").await?;
+    assert_eq!(response.text().await?, "hello world");
+
+    let stat = minio.stat_object("public-bucket", "hello.txt").await?;
+    assert!(stat.is_some());
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    for request in requests {
+        assert!(request.header("authorization").is_none());
+        assert!(request.header("x-amz-content-sha256").is_none());
+    }
+
+    let presigned = minio
+        .presigned_get_object(PresignedArgs::new("public-bucket", "hello.txt"))
+        .await;
+    assert!(presigned.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_build_without_provider() {
+    assert!(Minio::builder().endpoint("localhost:9022").build().is_err());
+}
+
+fn signed_minio() -> Minio {
+    Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new(
+            "minio-access-key-test",
+            "minio-secret-key-test",
+            Some("session-token".to_string()),
+        ))
+        .secure(false)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_presigned_post_policy() -> Result<()> {
+    let minio = signed_minio();
+    let expiration = UtcTime::new("2030-01-01T00:00:00Z".parse().unwrap());
+    let (url, form_data) = minio
+        .presigned_post_policy(
+            PostPolicy::new("bucket", expiration)
+                .key_starts_with("uploads/")
+                .content_type("image/png")
+                .content_length_range(1, 1024)
+                .success_action_status(201)
+                .metadata("owner", "alice"),
+        )
+        .await?;
+    assert_eq!(url, "http://localhost:9022/bucket");
+    assert_eq!(form_data["x-amz-algorithm"], "AWS4-HMAC-SHA256");
+    assert_eq!(form_data["x-amz-security-token"], "session-token");
+    assert_eq!(form_data["Content-Type"], "image/png");
+    assert_eq!(form_data["success_action_status"], "201");
+    assert_eq!(form_data["x-amz-meta-owner"], "alice");
+    assert!(!form_data.contains_key("key"));
+    assert!(form_data["x-amz-credential"].starts_with("minio-access-key-test/"));
+    assert!(form_data["x-amz-credential"].ends_with("/us-east-1/s3/aws4_request"));
+
+    let policy = base64::engine::general_purpose::STANDARD
+        .decode(&form_data["policy"])
+        .unwrap();
+    let policy: serde_json::Value = serde_json::from_slice(&policy).unwrap();
+    assert_eq!(policy["expiration"], "2030-01-01T00:00:00.000Z");
+    let conditions = policy["conditions"].as_array().unwrap();
+    for condition in [
+        serde_json::json!(["eq", "$bucket", "bucket"]),
+        serde_json::json!(["starts-with", "$key", "uploads/"]),
+        serde_json::json!(["content-length-range", 1, 1024]),
+        serde_json::json!(["eq", "$x-amz-date", form_data["x-amz-date"]]),
+        serde_json::json!(["eq", "$x-amz-security-token", "session-token"]),
+    ] {
+        assert!(conditions.contains(&condition), "{condition}");
+    }
+
+    let date = UtcTime::new(
+        chrono::NaiveDateTime::parse_from_str(&form_data["x-amz-date"], "%Y%m%dT%H%M%SZ")
+            .unwrap()
+            .and_utc(),
+    );
+    assert_eq!(
+        form_data["x-amz-signature"],
+        post_presign_v4(
+            &form_data["policy"],
+            "minio-secret-key-test",
+            &date,
+            "us-east-1"
+        )
+    );
+
+    let missing_key = minio
+        .presigned_post_policy(PostPolicy::new("bucket", expiration))
+        .await;
+    assert!(missing_key.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_presign() -> Result<()> {
+    let minio = signed_minio();
+
+    let delete = minio
+        .presign(
+            Method::DELETE,
+            PresignedArgs::new("bucket", "file.txt").expires(60),
+        )
+        .await?;
+    assert!(delete.starts_with("http://localhost:9022/bucket/file.txt?"));
+    assert!(delete.contains("X-Amz-Expires=60&"));
+    assert!(delete.contains("X-Amz-Security-Token=session-token"));
+    assert!(delete.contains("X-Amz-SignedHeaders=host&"));
+
+    let list = minio
+        .presign(
+            Method::GET,
+            PresignedArgs::for_bucket("bucket").query("list-type", "2"),
+        )
+        .await?;
+    assert!(list.starts_with("http://localhost:9022/bucket?"));
+    assert!(list.contains("list-type=2"));
+
+    let upload_part = minio
+        .presign(
+            Method::PUT,
+            PresignedArgs::new("bucket", "file.txt")
+                .query("uploadId", "upload-id")
+                .query("partNumber", "1")
+                .signed_header("content-type", "application/octet-stream"),
+        )
+        .await?;
+    assert!(upload_part.contains("partNumber=1"));
+    assert!(upload_part.contains("uploadId=upload-id"));
+    assert!(upload_part.contains("X-Amz-SignedHeaders=content-type%3Bhost&"));
+
+    let expires = minio
+        .presign(
+            Method::HEAD,
+            PresignedArgs::new("bucket", "file.txt").expires(0),
+        )
+        .await;
+    assert!(expires.is_err());
+
+    // a header that can't be sent fails, instead of a url without it
+    let invalid = minio
+        .presign(
+            Method::GET,
+            PresignedArgs::new("bucket", "file.txt")
+                .signed_header("x-amz-server-side-encryption-customer-key", "key\n"),
+        )
+        .await;
+    assert!(matches!(invalid, Err(Error::ValueError(_))));
+    let invalid = minio
+        .presign(
+            Method::GET,
+            PresignedArgs::new("bucket", "file.txt").header("response-content-type", "text\r"),
+        )
+        .await;
+    assert!(matches!(invalid, Err(Error::ValueError(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_presigned_upload_parts() -> Result<()> {
+    let (endpoint, requests) = stand_in(|r| {
+        if r.path.ends_with("?uploads") || r.path.ends_with("?uploads=") {
+            (
+                200,
+                r#"<InitiateMultipartUploadResult>
+  <Bucket>bucket</Bucket>
+  <Key>big-file.bin</Key>
+  <UploadId>upload-id</UploadId>
+</InitiateMultipartUploadResult>"#
+                    .to_string(),
+            )
+        } else {
+            (
+                200,
+                r#"<CompleteMultipartUploadResult>
+  <Location>http://localhost/bucket/big-file.bin</Location>
+  <Bucket>bucket</Bucket>
+  <Key>big-file.bin</Key>
+  <ETag>"3858f62230ac3c915f300c664312c11f-2"</ETag>
+</CompleteMultipartUploadResult>"#
+                    .to_string(),
+            )
+        }
+    })
+    .await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .secure(false)
+        .build()
+        .unwrap();
+    let task = minio
+        .create_multipart_upload("bucket", "big-file.bin")
+        .await?;
+
+    let urls = minio.presigned_upload_parts(&task, 3, 3600).await?;
+    assert_eq!(urls.len(), 3);
+    for (i, url) in urls.iter().enumerate() {
+        assert!(url.starts_with(&format!("{endpoint}/bucket/big-file.bin?")));
+        assert!(url.contains(&format!("partNumber={}&", i + 1)));
+        assert!(url.contains("uploadId=upload-id"));
+        assert!(url.contains("X-Amz-Expires=3600&"));
+    }
+    assert!(minio.presigned_upload_parts(&task, 0, 3600).await.is_err());
+
+    let result = minio
+        .complete_presigned_upload_parts(&task, vec!["\"etag-1\"".into(), "\"etag-2\"".into()])
+        .await?;
+    assert_eq!(result.key, "big-file.bin");
+    let complete = requests.lock().unwrap().last().cloned().unwrap();
+    assert_eq!(complete.method, "POST");
+    assert!(complete.path.contains("uploadId=upload-id"));
+    assert!(complete.body.contains("<PartNumber>1</PartNumber>"));
+    assert!(complete.body.contains("<PartNumber>2</PartNumber>"));
+    assert!(complete.body.contains("etag-2"));
+
+    assert!(minio
+        .complete_presigned_upload_parts(&task, vec![])
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_put_object_stream_trailing_checksum() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .multi_chunked_encoding(true)
+        .secure(false)
+        .build()
+        .unwrap();
+    let chunks: Vec<Result<Bytes>> = vec![Ok(Bytes::from("Hello ")), Ok(Bytes::from("world"))];
+    minio
+        .put_object_stream(
+            "bucket",
+            KeyArgs::new("hello.txt").checksum_algorithm(Some(ChecksumAlgorithm::CRC32)),
+            Box::pin(futures::stream::iter(chunks)),
+            Some(11),
+        )
+        .await?;
+
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(request.method, "PUT");
+    assert_eq!(
+        request.header("x-amz-content-sha256"),
+        Some("STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER")
+    );
+    assert_eq!(
+        request.header("x-amz-trailer"),
+        Some("x-amz-checksum-crc32")
+    );
+    assert_eq!(request.header("content-encoding"), Some("aws-chunked"));
+    assert!(request.body.starts_with("6;chunk-signature="));
+    assert!(request.body.contains("\r\nHello \r\n5;chunk-signature="));
+    assert!(request.body.contains("\r\n0;chunk-signature="));
+    assert!(request
+        .body
+        .contains("\r\nx-amz-checksum-crc32:i9aeUg==\r\nx-amz-trailer-signature:"));
+    assert!(request.body.ends_with("\r\n\r\n"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_put_object_checksum() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let minio = test_minio(&endpoint);
+    let key = KeyArgs::new("hello.txt").checksum_algorithm(Some(ChecksumAlgorithm::CRC32C));
+    minio
+        .put_object("bucket", key, Bytes::from("Hello world"))
+        .await?;
+
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(request.header("x-amz-checksum-crc32c"), Some("crUfeA=="));
+    assert_eq!(request.body, "Hello world");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multipart_upload_checksum() -> Result<()> {
+    let (endpoint, requests) = stand_in_with_headers(|r| {
+        if r.path.contains("uploads") {
+            let key = r.path.trim_start_matches("/bucket/").split('?').next();
+            let body = format!(
+                "<InitiateMultipartUploadResult><Bucket>bucket</Bucket>\
+                <Key>{}</Key><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>",
+                key.unwrap()
+            );
+            (200, vec![], body)
+        } else if r.method == "PUT" {
+            let e_tag = format!("\"{}\"", r.body.trim());
+            (200, vec![("ETag".to_string(), e_tag)], String::new())
+        } else {
+            let checksum = if r.path.contains("mismatch") {
+                "AAAAAA==-2"
+            } else {
+                "IDnaDw==-2"
+            };
+            let body = format!(
+                "<CompleteMultipartUploadResult><Location>location</Location>\
+                <Bucket>bucket</Bucket><Key>hello.txt</Key><ETag>etag</ETag>\
+                <ChecksumCRC32>{checksum}</ChecksumCRC32></CompleteMultipartUploadResult>"
+            );
+            (200, vec![], body)
+        }
+    })
+    .await;
+    let minio = test_minio(&endpoint);
+    let key = KeyArgs::new("hello.txt").checksum_algorithm(Some(ChecksumAlgorithm::CRC32));
+    let task = minio.create_multipart_upload("bucket", key).await?;
+    assert_eq!(task.checksum_algorithm(), Some(&ChecksumAlgorithm::CRC32));
+    let mut parts = vec![];
+    for (i, data) in ["Hello ", "world"].into_iter().enumerate() {
+        parts.push(minio.upload_part(&task, i + 1, Bytes::from(data)).await?);
+    }
+    assert_eq!(parts[1].checksum_crc32.as_deref(), Some("OncRQw=="));
+    let result = minio
+        .complete_multipart_upload(&task, parts.clone(), None)
+        .await?;
+    assert_eq!(result.checksum_crc32.as_deref(), Some("IDnaDw==-2"));
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(
+        requests[0].header("x-amz-checksum-algorithm"),
+        Some("CRC32")
+    );
+    assert_eq!(requests[2].header("x-amz-checksum-crc32"), Some("OncRQw=="));
+    assert!(requests[3]
+        .body
+        .contains("<PartNumber>2</PartNumber><ChecksumCRC32>OncRQw==</ChecksumCRC32>"));
+
+    let key = KeyArgs::new("mismatch.txt").checksum_algorithm(Some(ChecksumAlgorithm::CRC32));
+    let task = minio.create_multipart_upload("bucket", key).await?;
+    assert!(minio
+        .complete_multipart_upload(&task, parts, None)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_object_checksum_mode() -> Result<()> {
+    let (endpoint, requests) = stand_in_with_headers(|r| {
+        let checksum = if r.path.contains("corrupt") {
+            "AAAAAA=="
+        } else {
+            "i9aeUg=="
+        };
+        let headers = vec![("x-amz-checksum-crc32".to_string(), checksum.to_string())];
+        let body = if r.method == "HEAD" {
+            ""
+        } else {
+            "Hello world"
+        };
+        (200, headers, body.to_string())
+    })
+    .await;
+    let minio = test_minio(&endpoint);
+
+    let key = KeyArgs::new("hello.txt").checksum_mode(true);
+    let res = minio.get_object("bucket", key).await?;
+    let mut stream = checksum_verified_stream(res);
+    let mut body = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        body.extend_from_slice(&chunk?);
+    }
+    assert_eq!(body, b"Hello world");
+    assert_eq!(
+        requests.lock().unwrap()[0].header("x-amz-checksum-mode"),
+        Some("ENABLED")
+    );
+
+    let res = minio.get_object("bucket", "corrupt.txt").await?;
+    let items: Vec<_> = checksum_verified_stream(res).collect().await;
+    assert!(items.first().is_some_and(|i| i.is_ok()));
+    assert!(items.last().is_some_and(|i| i.is_err()));
+
+    let key = KeyArgs::new("hello.txt").checksum_mode(true);
+    let stat = minio.stat_object("bucket", key).await?.unwrap();
+    assert_eq!(
+        stat.checksum(),
+        Some(&(ChecksumAlgorithm::CRC32, "i9aeUg==".to_string()))
+    );
+    assert_eq!(
+        requests.lock().unwrap()[2].header("x-amz-checksum-mode"),
+        Some("ENABLED")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_v4a() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .signature_version(SignatureVersion::V4A)
+        .region_set("us-east-1,us-west-2")
+        .secure(false)
+        .build()
+        .unwrap();
+    assert_eq!(minio.signature_version(), SignatureVersion::V4A);
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(
+        request.header("x-amz-region-set"),
+        Some("us-east-1,us-west-2")
+    );
+    let authorization = request.header("authorization").unwrap();
+    assert!(authorization.starts_with("AWS4-ECDSA-P256-SHA256 Credential=access-key/"));
+    assert!(authorization.contains("/s3/aws4_request, SignedHeaders="));
+    assert!(authorization.contains(";x-amz-region-set, Signature=30"));
+    assert_eq!(request.body, "Hello world");
+
+    let url = minio
+        .presign(Method::GET, PresignedArgs::new("bucket", "hello.txt"))
+        .await?;
+    assert!(url.contains("X-Amz-Algorithm=AWS4-ECDSA-P256-SHA256&"));
+    assert!(url.contains("X-Amz-Region-Set=us-east-1%2Cus-west-2&"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_v4a_stream_over_http() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .signature_version(SignatureVersion::V4A)
+        .multi_chunked_encoding(true)
+        .secure(false)
+        .build()
+        .unwrap();
+    let chunks: Vec<Result<Bytes>> = vec![Ok(Bytes::from("Hello ")), Ok(Bytes::from("world"))];
+    minio
+        .put_object_stream(
+            "bucket",
+            "hello.txt",
+            Box::pin(futures::stream::iter(chunks)),
+            Some(11),
+        )
+        .await?;
+
+    // over plain http the stream is buffered and its payload signed
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(
+        request.header("x-amz-content-sha256"),
+        Some("64ec88ca00b268e5ba1a35678a1b5316d212f4f366b2477232534a8aeca37f3c")
+    );
+    assert_eq!(request.header("x-amz-trailer"), None);
+    assert_eq!(request.header("content-encoding"), None);
+    assert_eq!(request.body, "Hello world");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_v2() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .signature_version(SignatureVersion::V2)
+        .secure(false)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+
+    let request = requests.lock().unwrap()[0].clone();
+    let authorization = request.header("authorization").unwrap();
+    assert!(authorization.starts_with("AWS access-key:"));
+    assert!(request.header("date").is_some());
+    assert!(request.header("x-amz-content-sha256").is_none());
+    assert_eq!(request.body, "Hello world");
+
+    let url = minio
+        .presign(Method::GET, PresignedArgs::new("bucket", "hello.txt"))
+        .await?;
+    assert!(url.contains("/bucket/hello.txt?AWSAccessKeyId=access-key&Expires="));
+    assert!(url.contains("&Signature="));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clock_skew_correction() -> Result<()> {
+    // the clock of the server is an hour ahead.
+    let server_time = || UtcTime::now().add_seconds(3600);
+    let (endpoint, requests) = stand_in_with_headers(move |r| {
+        let now = server_time();
+        let date = r
+            .header("x-amz-date")
+            .and_then(UtcTime::from_aws_format_time)
+            .unwrap();
+        let headers = vec![("Date".to_string(), now.http_format_time())];
+        if (now.timestamp() - date.timestamp()).abs() > 900 {
+            let body = "<Error><Code>RequestTimeTooSkewed</Code>\
+                <Message>The difference between the request time and the server's time is too large.</Message>\
+                </Error>";
+            (403, headers, body.to_string())
+        } else {
+            (200, headers, String::new())
+        }
+    })
+    .await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .secure(false)
+        .build()
+        .unwrap();
+    assert_eq!(minio.clock_offset(), 0);
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+
+    let offset = minio.clock_offset();
+    assert!((3599..=3601).contains(&offset), "offset: {offset}");
+    {
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].body, "Hello world");
+    }
+    minio.stat_object("bucket", "hello.txt").await?;
+    assert_eq!(requests.lock().unwrap().len(), 3);
+
+    let url = minio
+        .presign(Method::GET, PresignedArgs::new("bucket", "hello.txt"))
+        .await?;
+    let date = server_time().aws_format_date();
+    assert!(url.contains(&format!("%2F{date}%2F")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clock_skew_error_code() -> Result<()> {
+    // a proxy drops the `Date` header, the server time is read from the error.
+    let (endpoint, requests) = stand_in(|r| {
+        let server_time = UtcTime::now().add_seconds(-7200);
+        let date = r
+            .header("x-amz-date")
+            .and_then(UtcTime::from_aws_format_time)
+            .unwrap();
+        if (server_time.timestamp() - date.timestamp()).abs() > 900 {
+            let body = format!(
+                "<Error><Code>RequestTimeTooSkewed</Code>\
+                <Message>The difference between the request time and the current time is too large.</Message>\
+                <ServerTime>{}</ServerTime></Error>",
+                server_time.format_time()
+            );
+            (403, body)
+        } else {
+            (200, String::new())
+        }
+    })
+    .await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .secure(false)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    let offset = minio.clock_offset();
+    assert!((-7201..=-7199).contains(&offset), "offset: {offset}");
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    // another error with a skewed `Date` header keeps the offset.
+    let (endpoint, requests) = stand_in_with_headers(|_| {
+        let date = UtcTime::now().add_seconds(3600).http_format_time();
+        let body = "<Error><Code>AccessDenied</Code><Message>Access Denied.</Message><RequestId>1</RequestId></Error>";
+        (403, vec![("Date".to_string(), date)], body.to_string())
+    })
+    .await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .secure(false)
+        .build()
+        .unwrap();
+    let err = minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::S3Error(e) if e.code == "AccessDenied"),
+        "{err:?}"
+    );
+    assert_eq!(minio.clock_offset(), 0);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+fn error_body(code: &str) -> String {
+    format!(
+        "<Error><Code>{code}</Code><Message>{code}</Message><Resource>/bucket/hello.txt</Resource>\
+        <RequestId>1</RequestId></Error>"
+    )
+}
+
+#[tokio::test]
+async fn test_retry_policy() -> Result<()> {
+    // every third request succeeds.
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let (endpoint, requests) =
+        stand_in(move |_| match counter.fetch_add(1, Ordering::SeqCst) % 3 {
+            0 => (503, error_body("SlowDown")),
+            1 => (400, error_body("RequestTimeout")),
+            _ => (200, String::new()),
+        })
+        .await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .secure(false)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    assert_eq!(requests.lock().unwrap().len(), 3);
+    assert!(requests
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|r| r.body == "Hello world"));
+
+    // the retries run out.
+    let err = minio
+        .executor(Method::PUT)
+        .bucket_name("bucket")
+        .object_name("hello.txt")
+        .retry_policy(RetryPolicy::new().max_attempts(2).jitter(false))
+        .send_ok()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::S3Error(e) if e.code == "RequestTimeout"));
+    assert_eq!(requests.lock().unwrap().len(), 5);
+
+    // a stream is only sent once.
+    count.store(0, Ordering::SeqCst);
+    let chunks: Vec<Result<Bytes>> = vec![Ok(Bytes::from("Hello world"))];
+    let res = minio
+        .executor(Method::PUT)
+        .bucket_name("bucket")
+        .object_name("hello.txt")
+        .body(Data::Stream(Box::pin(futures::stream::iter(chunks)), 11))
+        .send()
+        .await?;
+    assert_eq!(res.status(), 503);
+    assert_eq!(requests.lock().unwrap().len(), 6);
+
+    // unless it is rewindable.
+    count.store(0, Ordering::SeqCst);
+    minio
+        .executor(Method::PUT)
+        .bucket_name("bucket")
+        .object_name("hello.txt")
+        .rewindable_body(|| {
+            let chunks: Vec<Result<Bytes>> = vec![Ok(Bytes::from("Hello world"))];
+            Data::Stream(Box::pin(futures::stream::iter(chunks)), 11)
+        })
+        .send_ok()
+        .await?;
+    assert_eq!(requests.lock().unwrap().len(), 9);
+
+    // a POST is only sent once.
+    count.store(0, Ordering::SeqCst);
+    let res = minio
+        .executor(Method::POST)
+        .bucket_name("bucket")
+        .object_name("hello.txt")
+        .query("uploads", "")
+        .send()
+        .await?;
+    assert_eq!(res.status(), 503);
+    assert_eq!(requests.lock().unwrap().len(), 10);
+
+    // unless the policy allows it.
+    count.store(0, Ordering::SeqCst);
+    minio
+        .executor(Method::POST)
+        .bucket_name("bucket")
+        .object_name("hello.txt")
+        .query("uploads", "")
+        .retry_policy(
+            RetryPolicy::new()
+                .base_delay(Duration::from_millis(1))
+                .retry_post(true),
+        )
+        .send_ok()
+        .await?;
+    assert_eq!(requests.lock().unwrap().len(), 13);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_connection_reset() -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // the first connection is closed without answer, like a reset by a proxy.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = listener.local_addr().unwrap().to_string();
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).await;
+            if counter.fetch_add(1, Ordering::SeqCst) > 0 {
+                let response = "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        }
+    });
+    let minio = Minio::builder()
+        .endpoint(endpoint)
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .secure(false)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello"))
+        .await?;
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_not_retryable() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (400, error_body("InvalidArgument"))).await;
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .secure(false)
+        .build()
+        .unwrap();
+    let err = minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::S3Error(e) if e.code == "InvalidArgument"));
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+/// Tags requests, records the responses and answers `stop` requests itself.
+#[derive(Default)]
+struct Recording {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Interceptor for Recording {
+    fn before_sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<reqwest::Response>> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("before_sign {} {}", method, uri.path()));
+        headers.insert("x-request-id", "42".parse()?);
+        if uri.path().ends_with("/stop") {
+            let response = hyper::Response::builder().status(204).body("").unwrap();
+            return Ok(Some(response.into()));
+        }
+        if uri.path().ends_with("/deny") {
+            return Err(minio_rsc::error::ValueError::from("denied").into());
+        }
+        Ok(None)
+    }
+
+    fn after_sign(
+        &self,
+        _method: &Method,
+        _uri: &Uri,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<reqwest::Response>> {
+        let signed = headers
+            .get("authorization")
+            .is_some_and(|v| v.to_str().unwrap().contains("x-request-id"));
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("after_sign {signed}"));
+        headers.insert("x-tampered", "1".parse()?);
+        Ok(None)
+    }
+
+    fn after_response(
+        &self,
+        _method: &Method,
+        _uri: &Uri,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("after_response {}", response.status().as_u16()));
+        Ok(response)
+    }
+}
+
+#[tokio::test]
+async fn test_interceptor() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| (200, String::new())).await;
+    let interceptor = Recording::default();
+    let calls = interceptor.calls.clone();
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .interceptor(interceptor)
+        .secure(false)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "before_sign PUT /bucket/hello.txt",
+            "after_sign true",
+            "after_response 200"
+        ]
+    );
+    {
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].header("x-request-id"), Some("42"));
+        assert_eq!(requests[0].header("x-tampered"), Some("1"));
+    }
+
+    // short-circuit with a response, or an error.
+    calls.lock().unwrap().clear();
+    let res = minio
+        .executor(Method::GET)
+        .bucket_name("bucket")
+        .object_name("stop")
+        .send()
+        .await?;
+    assert_eq!(res.status(), 204);
+    let err = minio.stat_object("bucket", "deny").await.unwrap_err();
+    assert!(matches!(err, minio_rsc::error::Error::ValueError(_)));
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "before_sign GET /bucket/stop",
+            "before_sign HEAD /bucket/deny"
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_transport() -> Result<()> {
+    let mock = MockTransport::new();
+    mock.on(
+        Method::PUT,
+        "/bucket/hello.txt",
+        MockResponse::s3_error(503, "SlowDown", "Please reduce your request rate."),
+    );
+    mock.on(
+        Method::PUT,
+        "/bucket/hello.txt",
+        MockResponse::ok().header("etag", "\"3e25960a79dbc69b674cd4ec67a72c62\""),
+    );
+    mock.on(
+        Method::GET,
+        "/bucket/hello.txt",
+        MockResponse::ok().body("Hello world"),
+    );
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    let text = minio
+        .get_object("bucket", "hello.txt")
+        .await?
+        .text()
+        .await?;
+    assert_eq!(text, "Hello world");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].method, Method::PUT);
+    assert_eq!(requests[1].path(), "/bucket/hello.txt");
+    assert_eq!(requests[1].uri.host(), Some("localhost"));
+    assert_eq!(requests[1].body, "Hello world");
+    assert!(requests[1]
+        .header("authorization")
+        .unwrap()
+        .starts_with("AWS4-HMAC-SHA256 Credential=access-key/"));
+
+    // routes without a response are not found.
+    mock.clear_requests();
+    let err = minio.get_object("bucket", "missing").await.unwrap_err();
+    assert!(matches!(err, Error::S3Error(e) if e.code == "NotFound"));
+    assert_eq!(mock.requests().len(), 1);
+    Ok(())
+}
+
+/// Keeps `operation bucket status error_code bytes_sent bytes_received retries` of each operation.
+#[derive(Default, Clone)]
+struct Metrics(Arc<Mutex<Vec<String>>>);
+
+impl MetricsRecorder for Metrics {
+    fn record(&self, m: &RequestMetrics) {
+        self.0.lock().unwrap().push(format!(
+            "{} {} {} {} {} {} {}",
+            m.operation,
+            m.bucket.unwrap_or("-"),
+            m.status.unwrap_or(0),
+            m.error_code.unwrap_or("-"),
+            m.bytes_sent,
+            m.bytes_received,
+            m.retries
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_recorder() -> Result<()> {
+    let mock = MockTransport::new();
+    mock.on(
+        Method::PUT,
+        "/bucket/hello.txt",
+        MockResponse::s3_error(503, "SlowDown", "Please reduce your request rate."),
+    );
+    mock.on(Method::PUT, "/bucket/hello.txt", MockResponse::ok());
+    mock.on(
+        Method::GET,
+        "/bucket/hello.txt",
+        MockResponse::ok().body("Hello world"),
+    );
+    mock.on(
+        Method::GET,
+        "/bucket/missing",
+        MockResponse::s3_error(404, "NoSuchKey", "The specified key does not exist."),
+    );
+    mock.on(Method::HEAD, "/bucket/missing", MockResponse::new(404));
+    let metrics = Metrics::default();
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .metrics_recorder(metrics.clone())
+        .transport(mock)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    let text = minio
+        .get_object("bucket", "hello.txt")
+        .await?
+        .text()
+        .await?;
+    assert_eq!(text, "Hello world");
+
+    // the error response is still readable after its code is recorded.
+    let err = minio.get_object("bucket", "missing").await.unwrap_err();
+    assert!(matches!(err, Error::S3Error(e) if e.code == "NoSuchKey"));
+    assert!(minio.stat_object("bucket", "missing").await?.is_none());
+    assert_eq!(
+        *metrics.0.lock().unwrap(),
+        vec![
+            "put_object bucket 200 - 11 0 1",
+            "get_object bucket 200 - 0 11 0",
+            "get_object bucket 404 NoSuchKey 0 116 0",
+            "stat_object bucket 404 404 0 0 0",
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metrics_bytes_received() -> Result<()> {
+    let (endpoint, _) = stand_in_with_headers(|r| match r.path.split('?').next().unwrap() {
+        "/bucket/hello.txt" => (
+            200,
+            vec![("Transfer-Encoding".to_string(), "chunked".to_string())],
+            "Hello world".to_string(),
+        ),
+        _ => (
+            403,
+            vec![("Content-Type".to_string(), "text/html".to_string())],
+            "<html>Forbidden</html>".to_string(),
+        ),
+    })
+    .await;
+    let metrics = Metrics::default();
+    let minio = Minio::builder()
+        .endpoint(endpoint.trim_start_matches("http://"))
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .metrics_recorder(metrics.clone())
+        .secure(false)
+        .build()
+        .unwrap();
+
+    // the bytes of a chunked body are counted as it is read.
+    let res = minio.get_object("bucket", "hello.txt").await?;
+    assert_eq!(res.content_length(), None);
+    assert!(metrics.0.lock().unwrap().is_empty());
+    assert_eq!(res.text().await?, "Hello world");
+
+    // an error response without XML body is kept as is.
+    let res = minio
+        .executor(Method::GET)
+        .bucket_name("bucket")
+        .object_name("denied.html")
+        .send()
+        .await?;
+    assert_eq!(res.url().host_str(), Some("127.0.0.1"));
+    assert_eq!(res.url().path(), "/bucket/denied.html");
+    assert_eq!(res.text().await?, "<html>Forbidden</html>");
+    assert_eq!(
+        *metrics.0.lock().unwrap(),
+        vec![
+            "get_object bucket 200 - 0 11 0",
+            "request bucket 403 403 0 22 0",
+        ]
+    );
+
+    // the url of an error response is kept after its code is read.
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/bucket/missing",
+        MockResponse::s3_error(404, "NoSuchKey", "The specified key does not exist."),
+    );
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .metrics_recorder(metrics.clone())
+        .transport(mock)
+        .build()
+        .unwrap();
+    let res = minio
+        .executor(Method::GET)
+        .bucket_name("bucket")
+        .object_name("missing")
+        .send()
+        .await?;
+    assert_eq!(res.url().host_str(), Some("localhost"));
+    assert_eq!(res.url().path(), "/bucket/missing");
+    assert!(res.text().await?.contains("<Code>NoSuchKey</Code>"));
+    assert_eq!(
+        metrics.0.lock().unwrap()[2],
+        "request bucket 404 NoSuchKey 0 116 0"
+    );
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "invalid mock value of header etag")]
+fn test_mock_response_invalid_header() {
+    MockResponse::ok().header("etag", "\"1\"\n");
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "metrics")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyper::Method;
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use minio_rsc::client::{MetricsCrateRecorder, MetricsRecorder, RequestMetrics};
+
+/// A counter or histogram, the sum of the values.
+#[derive(Default)]
+struct Sum(AtomicU64);
+
+impl CounterFn for Sum {
+    fn increment(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::SeqCst);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.0.store(value, Ordering::SeqCst);
+    }
+}
+
+impl HistogramFn for Sum {
+    fn record(&self, value: f64) {
+        self.0.fetch_add((value * 1000.0) as u64, Ordering::SeqCst);
+    }
+}
+
+/// Keeps the metrics by `name{label=value,...}`, a stand-in for an exporter.
+#[derive(Default)]
+struct Sums(Mutex<HashMap<String, Arc<Sum>>>);
+
+impl Sums {
+    fn sum(&self, key: &Key) -> Arc<Sum> {
+        let labels: Vec<String> = key
+            .labels()
+            .map(|l| format!("{}={}", l.key(), l.value()))
+            .collect();
+        let name = format!("{}{{{}}}", key.name(), labels.join(","));
+        self.0.lock().unwrap().entry(name).or_default().clone()
+    }
+
+    fn get(&self, name: &str) -> Option<u64> {
+        let sums = self.0.lock().unwrap();
+        sums.get(name).map(|s| s.0.load(Ordering::SeqCst))
+    }
+}
+
+impl Recorder for Sums {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.sum(key))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.sum(key))
+    }
+}
+
+#[test]
+fn test_metrics_crate_recorder() {
+    let sums = Sums::default();
+    metrics::with_local_recorder(&sums, || {
+        let recorder = MetricsCrateRecorder::new();
+        let mut m = RequestMetrics {
+            operation: "put_object",
+            bucket: Some("bucket"),
+            method: &Method::PUT,
+            status: Some(200),
+            error_code: None,
+            latency: Duration::from_millis(120),
+            bytes_sent: 11,
+            bytes_received: 0,
+            retries: 1,
+        };
+        recorder.record(&m);
+        m.status = Some(404);
+        m.error_code = Some("NoSuchKey");
+        m.operation = "get_object";
+        m.bytes_sent = 0;
+        m.bytes_received = 111;
+        m.retries = 0;
+        recorder.record(&m);
+    });
+    let labels = "operation=put_object,bucket=bucket";
+    assert_eq!(
+        sums.get(&format!("minio_requests_total{{{labels},status=200}}")),
+        Some(1)
+    );
+    assert_eq!(
+        sums.get(&format!("minio_request_duration_seconds{{{labels}}}")),
+        Some(120)
+    );
+    assert_eq!(
+        sums.get(&format!("minio_request_retries_total{{{labels}}}")),
+        Some(1)
+    );
+    assert_eq!(
+        sums.get(&format!("minio_bytes_sent_total{{{labels}}}")),
+        Some(11)
+    );
+    assert_eq!(
+        sums.get(&format!(
+            "minio_request_errors_total{{{labels},code=NoSuchKey}}"
+        )),
+        None
+    );
+
+    let labels = "operation=get_object,bucket=bucket";
+    assert_eq!(
+        sums.get(&format!("minio_requests_total{{{labels},status=404}}")),
+        Some(1)
+    );
+    assert_eq!(
+        sums.get(&format!(
+            "minio_request_errors_total{{{labels},code=NoSuchKey}}"
+        )),
+        Some(1)
+    );
+    assert_eq!(
+        sums.get(&format!("minio_bytes_received_total{{{labels}}}")),
+        Some(111)
+    );
+}
@@ -0,0 +1,689 @@
+mod support;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use hyper::Method;
+use minio_rsc::client::{MinioBuilder, PresignedArgs};
+use minio_rsc::error::{Error, Result};
+use minio_rsc::provider::{
+    AssumeRoleProvider, AwsFileProvider, CachingProvider, ChainProvider, CredentialFuture,
+    DefaultProvider, EcsProvider, EnvAwsProvider, ImdsProvider, LdapIdentityProvider,
+    McConfigProvider, Provider, StaticProvider, WebIdentityProvider,
+};
+use minio_rsc::{Credentials, Minio, SignatureVersion};
+use support::stand_in;
+
+fn sts_credentials_xml(action: &str, access_key: &str, expiration: &str) -> String {
+    format!(
+        r#"<{action}Response xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+  <{action}Result>
+    <Credentials>
+      <AccessKeyId>{access_key}</AccessKeyId>
+      <SecretAccessKey>sYLRKS1Z7hSjluf6gEbb9066hnx315wHTiACPAjg</SecretAccessKey>
+      <Expiration>{expiration}</Expiration>
+      <SessionToken>eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9</SessionToken>
+    </Credentials>
+  </{action}Result>
+  <ResponseMetadata>
+    <RequestId>c6104cbe-af31-11e0-8154-cbc7ccf896c7</RequestId>
+  </ResponseMetadata>
+</{action}Response>"#
+    )
+}
+
+#[tokio::test]
+async fn test_assume_role_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| {
+        (
+            200,
+            sts_credentials_xml("AssumeRole", "Y4RJU1RNFGK48LGO9I2S", "2099-08-08T20:26:12Z"),
+        )
+    })
+    .await;
+    let provider = AssumeRoleProvider::new(endpoint, "base-access-key", "base-secret-key")
+        .duration_seconds(3600)
+        .policy(r#"{"Version":"2012-10-17"}"#);
+
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "Y4RJU1RNFGK48LGO9I2S");
+    assert_eq!(
+        credentials.session_token().unwrap(),
+        "eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9"
+    );
+    assert_eq!(credentials.expiration(), Some(4089903972));
+
+    // served from the cache
+    provider.fetch().await?;
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/");
+    assert!(request.body.contains("Action=AssumeRole"));
+    assert!(request.body.contains("Version=2011-06-15"));
+    assert!(request.body.contains("DurationSeconds=3600"));
+    let authorization = request.header("authorization").unwrap();
+    assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=base-access-key/"));
+    assert!(authorization.contains("/us-east-1/sts/aws4_request"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assume_role_provider_refresh() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| {
+        (
+            200,
+            sts_credentials_xml("AssumeRole", "Y4RJU1RNFGK48LGO9I2S", "2019-08-08T20:26:12Z"),
+        )
+    })
+    .await;
+    let provider = AssumeRoleProvider::new(endpoint, "base-access-key", "base-secret-key");
+    provider.fetch().await?;
+    provider.fetch().await?;
+    // expired credentials are never served from the cache
+    assert_eq!(requests.lock().unwrap().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assume_role_provider_error() {
+    let (endpoint, _) = stand_in(|_| {
+        (
+            403,
+            r#"<ErrorResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+  <Error>
+    <Type></Type>
+    <Code>InvalidClientTokenId</Code>
+    <Message>The security token included in the request is invalid.</Message>
+  </Error>
+  <RequestId>17B2E7E1F8DF9C1A</RequestId>
+</ErrorResponse>"#
+                .to_string(),
+        )
+    })
+    .await;
+    let provider = AssumeRoleProvider::new(endpoint, "base-access-key", "base-secret-key");
+    match provider.fetch().await {
+        Err(Error::S3Error(e)) => {
+            assert_eq!(e.code, "InvalidClientTokenId");
+            assert_eq!(e.request_id, "17B2E7E1F8DF9C1A");
+        }
+        _ => panic!("expected S3Error"),
+    }
+
+    let (endpoint, _) = stand_in(|_| (502, "<html>Bad Gateway</html>".to_string())).await;
+    let provider = AssumeRoleProvider::new(endpoint, "base-access-key", "base-secret-key");
+    match provider.fetch().await {
+        Err(Error::UnknownResponse(res)) => {
+            assert_eq!(res.status(), 502);
+            assert_eq!(res.text().await.unwrap(), "<html>Bad Gateway</html>");
+        }
+        _ => panic!("expected UnknownResponse"),
+    }
+}
+
+#[tokio::test]
+async fn test_web_identity_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| {
+        (
+            200,
+            sts_credentials_xml(
+                "AssumeRoleWithWebIdentity",
+                "WEBIDENTITYACCESSKEY",
+                "2099-08-08T20:26:12Z",
+            ),
+        )
+    })
+    .await;
+    let path = std::env::temp_dir().join("minio-rsc-test-web-identity-token");
+    std::fs::write(&path, "eyJhbGciOiJSUzI1NiJ9.first\n")?;
+    let provider = WebIdentityProvider::from_file(endpoint, &path)
+        .role_arn("arn:minio:iam:::role/dummy")
+        .duration_seconds(3600);
+
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "WEBIDENTITYACCESSKEY");
+    provider.fetch().await?;
+
+    // the token file is read again on refresh
+    std::fs::write(&path, "eyJhbGciOiJSUzI1NiJ9.second")?;
+    provider.assume_role().await?;
+    std::fs::remove_file(&path)?;
+    assert!(matches!(
+        provider.assume_role().await,
+        Err(Error::IoError(_))
+    ));
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].header("authorization").is_none());
+    assert!(requests[0]
+        .body
+        .contains("Action=AssumeRoleWithWebIdentity"));
+    assert!(requests[0]
+        .body
+        .contains("WebIdentityToken=eyJhbGciOiJSUzI1NiJ9.first&"));
+    assert!(requests[0]
+        .body
+        .contains("RoleArn=arn%3Aminio%3Aiam%3A%3A%3Arole%2Fdummy"));
+    assert!(requests[1]
+        .body
+        .contains("WebIdentityToken=eyJhbGciOiJSUzI1NiJ9.second&"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_grants_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| {
+        (
+            200,
+            sts_credentials_xml(
+                "AssumeRoleWithClientGrants",
+                "CLIENTGRANTSACCESSKEY",
+                "2099-08-08T20:26:12Z",
+            ),
+        )
+    })
+    .await;
+    let provider =
+        WebIdentityProvider::from_fn(endpoint, || Ok("eyJhbGciOiJSUzI1NiJ9".to_string()))
+            .client_grants(true);
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "CLIENTGRANTSACCESSKEY");
+    let requests = requests.lock().unwrap().clone();
+    assert!(requests[0]
+        .body
+        .contains("Action=AssumeRoleWithClientGrants"));
+    assert!(requests[0].body.contains("Token=eyJhbGciOiJSUzI1NiJ9"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ldap_identity_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|_| {
+        (
+            200,
+            sts_credentials_xml(
+                "AssumeRoleWithLDAPIdentity",
+                "LDAPACCESSKEY",
+                "2099-08-08T20:26:12Z",
+            ),
+        )
+    })
+    .await;
+    let provider = LdapIdentityProvider::new(endpoint, "dillon", "p@ss word").duration_seconds(900);
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "LDAPACCESSKEY");
+    provider.fetch().await?;
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].header("authorization").is_none());
+    let body = &requests[0].body;
+    assert!(body.contains("Action=AssumeRoleWithLDAPIdentity"));
+    assert!(body.contains("LDAPUsername=dillon"));
+    assert!(body.contains("LDAPPassword=p%40ss%20word"));
+    assert!(body.contains("DurationSeconds=900"));
+    Ok(())
+}
+
+struct FailProvider;
+
+impl Provider for FailProvider {
+    fn fetch(&self) -> CredentialFuture {
+        Box::pin(async { Err(Error::ValueError("fail provider".to_string())) })
+    }
+}
+
+#[tokio::test]
+async fn test_chain_provider() -> Result<()> {
+    let provider = ChainProvider::new()
+        .push(FailProvider)
+        .push(StaticProvider::new("first", "first-secret", None))
+        .push(StaticProvider::new("second", "second-secret", None));
+    assert_eq!(provider.fetch().await?.access_key(), "first");
+
+    let provider = ChainProvider::new().push(FailProvider).push(FailProvider);
+    assert!(provider.fetch().await.is_err());
+    Ok(())
+}
+
+/// Serializes the tests changing environment variables.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Sets environment variables, `None` removes one, restores them when dropped.
+struct EnvVars {
+    saved: Vec<(&'static str, Option<std::ffi::OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvVars {
+    fn set(vars: &[(&'static str, Option<&str>)]) -> Self {
+        let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut saved = Vec::new();
+        for (key, value) in vars {
+            saved.push((*key, std::env::var_os(key)));
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+        Self { saved, _lock: lock }
+    }
+}
+
+impl Drop for EnvVars {
+    fn drop(&mut self) {
+        for (key, value) in self.saved.drain(..) {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_env_aws_provider() -> Result<()> {
+    let _env = EnvVars::set(&[
+        ("AWS_ACCESS_KEY_ID", Some("aws-access-key")),
+        ("AWS_SECRET_ACCESS_KEY", Some("aws-secret-key")),
+        ("AWS_SESSION_TOKEN", Some("aws-session-token")),
+    ]);
+    let credentials = EnvAwsProvider.fetch().await?;
+    assert_eq!(credentials.access_key(), "aws-access-key");
+    assert_eq!(credentials.secret_key(), "aws-secret-key");
+    assert_eq!(credentials.session_token().unwrap(), "aws-session-token");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_default_provider() -> Result<()> {
+    let dir = std::env::temp_dir().join("minio-rsc-test-default-provider");
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(
+        dir.join("config.json"),
+        r#"{"version": "10", "aliases": {"local": {"url": "http://localhost:9022", "accessKey": "mc-access-key", "secretKey": "mc-secret-key"}}}"#,
+    )?;
+    let missing = dir.join("missing");
+    let missing = missing.to_str().unwrap();
+    let _env = EnvVars::set(&[
+        ("MINIO_ACCESS_KEY", None),
+        ("MINIO_SECRET_KEY", None),
+        ("AWS_ACCESS_KEY_ID", None),
+        ("AWS_ACCESS_KEY", None),
+        ("AWS_SECRET_ACCESS_KEY", None),
+        ("AWS_SHARED_CREDENTIALS_FILE", Some(missing)),
+        ("AWS_CONFIG_FILE", Some(missing)),
+        ("MC_CONFIG_DIR", dir.to_str()),
+        ("AWS_WEB_IDENTITY_TOKEN_FILE", None),
+        ("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI", None),
+        ("AWS_CONTAINER_CREDENTIALS_FULL_URI", None),
+        ("AWS_EC2_METADATA_DISABLED", Some("true")),
+    ]);
+    // the mc alias comes after the environment and the AWS files
+    let provider = DefaultProvider::new();
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "mc-access-key");
+
+    std::env::set_var("AWS_ACCESS_KEY_ID", "aws-access-key");
+    std::env::set_var("AWS_SECRET_ACCESS_KEY", "aws-secret-key");
+    let credentials = DefaultProvider::new().fetch().await?;
+    assert_eq!(credentials.access_key(), "aws-access-key");
+    // the resolved credentials are cached
+    assert_eq!(provider.fetch().await?.access_key(), "mc-access-key");
+    std::env::remove_var("AWS_ACCESS_KEY_ID");
+
+    // an alias without keys is skipped
+    std::fs::write(
+        dir.join("config.json"),
+        r#"{"version": "10", "aliases": {"local": {"url": "http://localhost:9000", "accessKey": "", "secretKey": ""}}}"#,
+    )?;
+    assert!(DefaultProvider::new().fetch().await.is_err());
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aws_file_provider() -> Result<()> {
+    let dir = std::env::temp_dir().join("minio-rsc-test-aws-file");
+    std::fs::create_dir_all(&dir)?;
+    let credentials_file = dir.join("credentials");
+    let config_file = dir.join("config");
+    std::fs::write(
+        &credentials_file,
+        r#"[default]
+aws_access_key_id = default-access-key
+aws_secret_access_key = default-secret-key
+
+[staging]
+aws_access_key_id = staging-access-key
+aws_secret_access_key = staging-secret-key
+aws_session_token = staging-session-token
+"#,
+    )?;
+    std::fs::write(
+        &config_file,
+        r#"[default]
+region = us-east-1
+
+[profile staging]
+region = eu-west-1
+endpoint_url = http://localhost:9022
+
+[profile local]
+aws_access_key_id = local-access-key
+aws_secret_access_key = local-secret-key
+"#,
+    )?;
+    let provider = AwsFileProvider::new()
+        .credentials_file(&credentials_file)
+        .config_file(&config_file);
+
+    let credentials = provider.clone().profile("default").fetch().await?;
+    assert_eq!(credentials.access_key(), "default-access-key");
+    assert!(credentials.session_token().is_none());
+
+    let staging = provider.clone().profile("staging");
+    let credentials = staging.fetch().await?;
+    assert_eq!(credentials.access_key(), "staging-access-key");
+    assert_eq!(
+        credentials.session_token().unwrap(),
+        "staging-session-token"
+    );
+    let profile = staging.load()?;
+    assert_eq!(profile.region.as_deref(), Some("eu-west-1"));
+    assert_eq!(
+        profile.endpoint_url.as_deref(),
+        Some("http://localhost:9022")
+    );
+
+    // credentials only in the config file
+    let credentials = provider.clone().profile("local").fetch().await?;
+    assert_eq!(credentials.secret_key(), "local-secret-key");
+
+    assert!(provider.clone().profile("missing").fetch().await.is_err());
+
+    let minio = Minio::builder().aws_profile(staging)?.build()?;
+    assert_eq!(minio.region(), "eu-west-1");
+    std::fs::remove_dir_all(&dir)?;
+    // the credentials are loaded once, the files are not read again.
+    let url = minio
+        .presign(Method::GET, PresignedArgs::new("bucket", "key"))
+        .await?;
+    assert!(url.contains("X-Amz-Credential=staging-access-key%2F"));
+    assert!(provider.profile("default").fetch().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mc_config_provider() -> Result<()> {
+    let path = std::env::temp_dir().join("minio-rsc-test-mc-config.json");
+    std::fs::write(
+        &path,
+        r#"{
+  "version": "10",
+  "aliases": {
+    "local": {
+      "url": "http://localhost:9022",
+      "accessKey": "mc-access-key",
+      "secretKey": "mc-secret-key",
+      "api": "s3v4",
+      "path": "auto"
+    },
+    "play": {
+      "url": "https://play.min.io",
+      "accessKey": "play-access-key",
+      "secretKey": "play-secret-key",
+      "api": "S3v4",
+      "path": "off"
+    },
+    "legacy": {
+      "url": "http://localhost:9022",
+      "accessKey": "legacy-access-key",
+      "secretKey": "legacy-secret-key",
+      "api": "S3v2",
+      "path": "auto"
+    }
+  }
+}"#,
+    )?;
+    let provider = McConfigProvider::new("local").config_file(&path);
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "mc-access-key");
+    assert_eq!(credentials.secret_key(), "mc-secret-key");
+
+    let alias = McConfigProvider::new("play").config_file(&path).load()?;
+    assert_eq!(alias.url, "https://play.min.io");
+    assert_eq!(alias.path, "off");
+
+    assert!(McConfigProvider::new("missing")
+        .config_file(&path)
+        .fetch()
+        .await
+        .is_err());
+
+    let minio = MinioBuilder::new().mc_alias(provider.clone())?.build()?;
+    assert_eq!(minio.signature_version(), SignatureVersion::V4);
+    let legacy = McConfigProvider::new("legacy").config_file(&path);
+    let minio = MinioBuilder::new().mc_alias(legacy)?.build()?;
+    assert_eq!(minio.signature_version(), SignatureVersion::V2);
+    std::fs::remove_file(&path)?;
+    // the credentials are loaded once, the file is not read again.
+    let url = minio
+        .presign(Method::GET, PresignedArgs::new("bucket", "key"))
+        .await?;
+    assert!(url.contains("AWSAccessKeyId=legacy-access-key"));
+    assert!(provider.fetch().await.is_err());
+    Ok(())
+}
+
+/// Counts fetches, returns credentials expiring in `expires_in` seconds, or an error if negative.
+struct CountProvider {
+    count: Arc<AtomicUsize>,
+    expires_in: i64,
+}
+
+impl Provider for CountProvider {
+    fn fetch(&self) -> CredentialFuture {
+        let n = self.count.fetch_add(1, Ordering::SeqCst);
+        let expires_in = self.expires_in;
+        Box::pin(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            if expires_in < 0 {
+                return Err(Error::ValueError(format!("count provider failed {n}")));
+            }
+            let exp = chrono::Utc::now().timestamp() + expires_in;
+            Ok(Credentials::new(
+                format!("access-key-{n}"),
+                "secret-key",
+                None,
+                Some(exp),
+            ))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_caching_provider() -> Result<()> {
+    let count = Arc::new(AtomicUsize::new(0));
+    let provider = CachingProvider::new(CountProvider {
+        count: count.clone(),
+        expires_in: 3600,
+    });
+    // concurrent fetches share a single refresh
+    let (a, b, c) = tokio::join!(provider.fetch(), provider.fetch(), provider.clone().fetch());
+    assert_eq!(a?.access_key(), "access-key-0");
+    assert_eq!(b?.access_key(), "access-key-0");
+    assert_eq!(c?.access_key(), "access-key-0");
+    provider.fetch().await?;
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    // credentials within the refresh window are refreshed
+    let count = Arc::new(AtomicUsize::new(0));
+    let provider = CachingProvider::new(CountProvider {
+        count: count.clone(),
+        expires_in: 3600,
+    })
+    .refresh_before(7200);
+    provider.fetch().await?;
+    assert_eq!(provider.fetch().await?.access_key(), "access-key-1");
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    // refresh failures are surfaced
+    let count = Arc::new(AtomicUsize::new(0));
+    let provider = CachingProvider::new(CountProvider {
+        count: count.clone(),
+        expires_in: -1,
+    });
+    let (a, b) = tokio::join!(provider.fetch(), provider.fetch());
+    assert!(a.is_err() && b.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert!(provider.fetch().await.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    // callers sharing a failed refresh all get the S3 error
+    let provider = CachingProvider::new(DenyProvider);
+    let (a, b) = tokio::join!(provider.fetch(), provider.fetch());
+    let mut shared = 0;
+    for result in [a, b] {
+        let err = result.unwrap_err();
+        match err.inner() {
+            Error::S3Error(e) => assert_eq!(e.code, "AccessDenied"),
+            e => panic!("expected S3Error, got {e:?}"),
+        }
+        // the shared error is the source of the wrapper
+        if let Error::Shared(_) = err {
+            let source = std::error::Error::source(&err).and_then(|e| e.downcast_ref::<Error>());
+            assert!(matches!(source, Some(Error::S3Error(_))));
+            shared += 1;
+        }
+    }
+    assert!(shared > 0);
+    Ok(())
+}
+
+/// Fails with an `AccessDenied` S3 error after a short delay.
+struct DenyProvider;
+
+impl Provider for DenyProvider {
+    fn fetch(&self) -> CredentialFuture {
+        Box::pin(async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            let body = "<Error><Code>AccessDenied</Code><Message>Access Denied.</Message><RequestId>1</RequestId></Error>";
+            Err(Error::S3Error(body.try_into()?))
+        })
+    }
+}
+
+#[test]
+fn test_credentials_expired() {
+    let now = chrono::Utc::now().timestamp();
+    assert!(!Credentials::new("ak", "sk", None, None).is_expired());
+    assert!(!Credentials::new("ak", "sk", None, Some(now + 3600)).is_expired());
+    assert!(Credentials::new("ak", "sk", None, Some(now + 5)).is_expired());
+    assert!(Credentials::new("ak", "sk", None, Some(now - 3600)).is_expired());
+}
+
+fn metadata_credentials_json(access_key: &str) -> String {
+    format!(
+        r#"{{
+  "Code": "Success",
+  "LastUpdated": "2024-01-01T00:00:00Z",
+  "Type": "AWS-HMAC",
+  "AccessKeyId": "{access_key}",
+  "SecretAccessKey": "metadata-secret-key",
+  "Token": "metadata-session-token",
+  "Expiration": "2099-08-08T20:26:12Z"
+}}"#
+    )
+}
+
+#[tokio::test]
+async fn test_imds_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|r| match (r.method.as_str(), r.path.as_str()) {
+        ("PUT", "/latest/api/token") => (200, "imds-session-token".to_string()),
+        (_, _) if r.header("x-aws-ec2-metadata-token") != Some("imds-session-token") => {
+            (401, String::new())
+        }
+        ("GET", "/latest/meta-data/iam/security-credentials/") => {
+            (200, "instance-role\n".to_string())
+        }
+        ("GET", "/latest/meta-data/iam/security-credentials/instance-role") => {
+            (200, metadata_credentials_json("IMDSACCESSKEY"))
+        }
+        _ => (404, String::new()),
+    })
+    .await;
+    let provider = ImdsProvider::new().endpoint(endpoint);
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "IMDSACCESSKEY");
+    assert_eq!(credentials.secret_key(), "metadata-secret-key");
+    assert_eq!(
+        credentials.session_token().unwrap(),
+        "metadata-session-token"
+    );
+    assert_eq!(credentials.expiration(), Some(4089903972));
+    provider.fetch().await?;
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0].header("x-aws-ec2-metadata-token-ttl-seconds"),
+        Some("21600")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ecs_provider() -> Result<()> {
+    let (endpoint, requests) = stand_in(|r| match r.header("authorization") {
+        Some("Basic YWJjOmRlZg==") => (200, metadata_credentials_json("ECSACCESSKEY")),
+        _ => (403, String::new()),
+    })
+    .await;
+    let provider = EcsProvider::new(format!("{endpoint}/v2/credentials/task"))
+        .authorization_token("Basic YWJjOmRlZg==");
+    let credentials = provider.fetch().await?;
+    assert_eq!(credentials.access_key(), "ECSACCESSKEY");
+    provider.fetch().await?;
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v2/credentials/task");
+
+    let provider = EcsProvider::new(format!("{endpoint}/v2/credentials/task"));
+    assert!(provider.fetch().await.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_ecs_provider_from_env() {
+    let from_full_uri = |uri: &str| {
+        let _env = EnvVars::set(&[
+            ("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI", None),
+            ("AWS_CONTAINER_CREDENTIALS_FULL_URI", Some(uri)),
+        ]);
+        EcsProvider::from_env().is_some()
+    };
+    assert!(from_full_uri("https://credentials.example.com/v1"));
+    assert!(from_full_uri("http://127.0.0.1:1338/v2/credentials"));
+    assert!(from_full_uri("http://localhost/v2/credentials"));
+    assert!(from_full_uri("http://[::1]:1338/v2/credentials"));
+    assert!(from_full_uri("http://169.254.170.2/v2/credentials"));
+    assert!(from_full_uri("http://169.254.170.23/v1/credentials"));
+    assert!(from_full_uri("http://[fd00:ec2::23]/v1/credentials"));
+    // the authorization token is not sent to arbitrary hosts over http
+    assert!(!from_full_uri("http://credentials.example.com/v1"));
+    assert!(!from_full_uri("http://10.0.0.1/v1"));
+    assert!(!from_full_uri("ftp://localhost/v1"));
+    assert!(!from_full_uri("not a url"));
+
+    let _env = EnvVars::set(&[
+        (
+            "AWS_CONTAINER_CREDENTIALS_RELATIVE_URI",
+            Some("/v2/credentials/task"),
+        ),
+        ("AWS_CONTAINER_CREDENTIALS_FULL_URI", None),
+    ]);
+    assert!(EcsProvider::from_env().is_some());
+}
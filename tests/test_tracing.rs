@@ -0,0 +1,192 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use hyper::Method;
+use minio_rsc::client::{MockResponse, MockTransport};
+use minio_rsc::error::Result;
+use minio_rsc::provider::StaticProvider;
+use minio_rsc::Minio;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// A span recorded by [Recorder], with the fields as debug strings.
+#[derive(Debug, Clone)]
+struct RecordedSpan {
+    name: &'static str,
+    parent: Option<u64>,
+    fields: HashMap<String, String>,
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// Records the spans, a stand-in for `tracing-subscriber`.
+#[derive(Clone, Default)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    stack: Arc<Mutex<Vec<u64>>>,
+}
+
+impl Recorder {
+    fn spans(&self, name: &str) -> Vec<RecordedSpan> {
+        let spans = self.spans.lock().unwrap();
+        spans.iter().filter(|s| s.name == name).cloned().collect()
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut fields = HashMap::new();
+        span.record(&mut Fields(&mut fields));
+        let parent = self.stack.lock().unwrap().last().copied();
+        self.spans.lock().unwrap().push(RecordedSpan {
+            name: span.metadata().name(),
+            parent,
+            fields,
+        });
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let span = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut Fields(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+#[tokio::test]
+async fn test_operation_spans() -> Result<()> {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::PUT,
+        "/bucket/hello.txt",
+        MockResponse::ok().header("x-amz-request-id", "17A2B6C3D4E5F607"),
+    );
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .transport(mock)
+        .build()
+        .unwrap();
+    minio
+        .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+        .await?;
+    assert!(minio.get_object("bucket", "missing").await.is_err());
+
+    let operations = recorder.spans("s3_operation");
+    assert_eq!(operations.len(), 2);
+    let put = &operations[0].fields;
+    assert_eq!(put["operation"], "put_object");
+    assert_eq!(put["bucket"], "bucket");
+    assert_eq!(put["key"], "hello.txt");
+    assert_eq!(put["method"], "PUT");
+    assert_eq!(put["region"], "us-east-1");
+    assert_eq!(put["bytes_sent"], "11");
+    assert_eq!(put["status"], "200");
+    assert_eq!(put["request_id"], "17A2B6C3D4E5F607");
+    assert_eq!(put["retries"], "0");
+    assert!(put.contains_key("latency_ms"));
+    let get = &operations[1].fields;
+    assert_eq!(get["operation"], "get_object");
+    assert_eq!(get["status"], "404");
+
+    // signing and sending are nested in the operation.
+    let spans = recorder.spans.lock().unwrap();
+    let sign = spans.iter().find(|s| s.name == "sign").unwrap();
+    assert_eq!(sign.parent, Some(1));
+    assert_eq!(sign.fields["version"], "V4");
+    let send = spans.iter().find(|s| s.name == "send").unwrap();
+    assert_eq!(send.parent, Some(1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_receive_span() -> Result<()> {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/bucket/hello.txt",
+        MockResponse::ok().body("Hello world"),
+    );
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .transport(mock)
+        .build()
+        .unwrap();
+    let res = minio.get_object("bucket", "hello.txt").await?;
+    assert!(!recorder.spans("s3_operation")[0]
+        .fields
+        .contains_key("bytes_received"));
+    assert_eq!(res.text().await?, "Hello world");
+
+    // the body is read in the operation, counting the bytes read.
+    assert_eq!(
+        recorder.spans("s3_operation")[0].fields["bytes_received"],
+        "11"
+    );
+    let receive = recorder.spans("receive");
+    assert_eq!(receive.len(), 1);
+    assert_eq!(receive[0].parent, Some(1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_is_send() -> Result<()> {
+    let mock = MockTransport::new();
+    mock.on(Method::PUT, "/bucket/hello.txt", MockResponse::ok());
+    let minio = Minio::builder()
+        .endpoint("localhost:9022")
+        .provider(StaticProvider::new("access-key", "secret-key", None))
+        .transport(mock)
+        .build()
+        .unwrap();
+    tokio::spawn(async move {
+        minio
+            .put_object("bucket", "hello.txt", Bytes::from("Hello world"))
+            .await
+    })
+    .await
+    .unwrap()?;
+    Ok(())
+}